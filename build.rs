@@ -0,0 +1,302 @@
+//! Generates the solution registry for the in-process runner.
+//! Every solution in `src/bin` is included as a module of the main binary, and its `SOLUTION` entry is added to a static table.
+//!
+//! Also generates the tests of the example manifests in `data/{year}/examples/{day}.json`.
+//! The `solution!` macro includes them into the tests of each solution.
+//!
+//! The version of the compiler is passed on as `AOC_RUSTC_VERSION`, to be stored with benchmarks.
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    path::Path,
+    process::Command,
+};
+
+use tinyjson::JsonValue;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    // e.g. `rustc 1.83.0 (90b35a623 2024-11-26)`.
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .and_then(|version| version.strip_prefix("rustc ").map(str::to_string))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=AOC_RUSTC_VERSION={rustc_version}");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    // solutions are named `{year}_{day}.rs`, e.g. `2024_01.rs`.
+    let mut puzzles: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name().into_string().ok()?;
+                    let (year, day) = name.strip_suffix(".rs")?.split_once('_')?;
+                    let is_number = |s: &str, len: usize| {
+                        s.len() == len && s.chars().all(|c| c.is_ascii_digit())
+                    };
+                    (is_number(year, 4) && is_number(day, 2))
+                        .then(|| (year.to_string(), day.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    puzzles.sort();
+
+    let mut out = String::from("// @generated by build.rs\n\n");
+
+    for (year, day) in &puzzles {
+        let path = bin_dir.join(format!("{year}_{day}.rs"));
+        out.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {:?}]\nmod y{year}_d{day};\n\n",
+            path.to_string_lossy()
+        ));
+    }
+
+    out.push_str("pub static SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[\n");
+    for (year, day) in &puzzles {
+        out.push_str(&format!("    y{year}_d{day}::SOLUTION,\n"));
+    }
+    out.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), out).unwrap();
+
+    let examples_dir = Path::new(&out_dir).join("examples");
+    fs::create_dir_all(&examples_dir).unwrap();
+
+    let mut years: Vec<&String> = puzzles.iter().map(|(year, _)| year).collect();
+    years.dedup();
+    for year in years {
+        let data_dir = Path::new(&manifest_dir)
+            .join("data")
+            .join(year)
+            .join("examples");
+        if data_dir.exists() {
+            println!("cargo:rerun-if-changed={}", data_dir.to_string_lossy());
+        }
+    }
+
+    // every solution includes its tests, so a file is written even if there is no manifest.
+    for (year, day) in &puzzles {
+        let manifest_path = Path::new(&manifest_dir)
+            .join("data")
+            .join(year)
+            .join("examples")
+            .join(format!("{day}.json"));
+        let source = fs::read_to_string(bin_dir.join(format!("{year}_{day}.rs"))).unwrap();
+
+        let tests = match fs::read_to_string(&manifest_path) {
+            Ok(manifest) => parse_function(&source)
+                .and_then(|parse| example_tests(&manifest, parse.as_deref()))
+                .unwrap_or_else(|e| panic!("invalid example manifest {manifest_path:?}: {e}")),
+            Err(_) => String::new(),
+        };
+
+        // `solution!` knows the day as a number, e.g. `2024_1.rs`.
+        let day = day.trim_start_matches('0');
+        fs::write(examples_dir.join(format!("{year}_{day}.rs")), tests).unwrap();
+    }
+}
+
+/// The parse function passed to `solution!`, e.g. `parse_input` for `solution!(2024, 16, parse = parse_input)`.
+/// The generated tests call it by name, so it has to be a path rather than an arbitrary expression.
+fn parse_function(source: &str) -> Result<Option<String>, String> {
+    const MACRO: &str = "solution!(";
+
+    // skip invocations in comments, e.g. `// solution!(2024, 1)`.
+    let Some(start) = source.match_indices(MACRO).map(|(i, _)| i).find(|i| {
+        let line_start = source[..*i].rfind('\n').map_or(0, |n| n + 1);
+        !source[line_start..*i].contains("//")
+    }) else {
+        return Ok(None);
+    };
+
+    // split the arguments at top-level commas, as they may contain parentheses, e.g. `parse = parse_with(8)`.
+    let mut args = vec![];
+    let mut arg = String::new();
+    let mut depth = 0;
+    let mut chars = source[start + MACRO.len()..].chars();
+    loop {
+        match chars.next() {
+            None => return Err("unterminated `solution!` invocation".to_string()),
+            Some(')') if depth == 0 => break,
+            Some(',') if depth == 0 => {
+                args.push(std::mem::take(&mut arg));
+                continue;
+            }
+            Some(c @ ('(' | '[' | '{')) => {
+                depth += 1;
+                arg.push(c);
+            }
+            Some(c @ (')' | ']' | '}')) => {
+                depth -= 1;
+                arg.push(c);
+            }
+            Some(c) => arg.push(c),
+        }
+    }
+    args.push(arg);
+
+    let Some(parse) = args.iter().find_map(|arg| {
+        let rest = arg.trim().strip_prefix("parse")?.trim_start();
+        Some(rest.strip_prefix('=')?.trim())
+    }) else {
+        return Ok(None);
+    };
+
+    if parse.is_empty()
+        || !parse
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == ':')
+    {
+        return Err(format!(
+            "the `parse` argument of `solution!` has to name a function to generate example tests, found `{parse}`"
+        ));
+    }
+    Ok(Some(parse.to_string()))
+}
+
+/// Generates a test for every example of a manifest.
+///
+/// ```json
+/// {
+///   "examples": [
+///     { "part": 1, "answer": 161 },
+///     { "part": 2, "file": "03_2.txt", "answer": 48 },
+///     { "name": "part_one_small", "part": 1, "input": "XMAS", "answer": 1 },
+///     { "part": 2, "function": "part_two_with_depth", "args": [25], "answer": 55312 },
+///     { "part": 2, "answer": 117440, "ignore": "not solved for all inputs yet" }
+///   ]
+/// }
+/// ```
+fn example_tests(manifest: &str, parse: Option<&str>) -> Result<String, String> {
+    let json: JsonValue = manifest.parse().map_err(|e| format!("{e}"))?;
+    let examples: &Vec<JsonValue> = json["examples"]
+        .get()
+        .ok_or("expected an `examples` array")?;
+
+    let mut out = String::from("// @generated by build.rs\n\n");
+    let mut names: HashSet<String> = HashSet::new();
+
+    for (index, example) in examples.iter().enumerate() {
+        let example: &HashMap<String, JsonValue> = example
+            .get()
+            .ok_or(format!("example {index}: expected an object"))?;
+        let field = |key: &str| example.get(key);
+        let error = |message: &str| format!("example {index}: {message}");
+
+        let part = match field("part").and_then(|p| p.get::<f64>()) {
+            Some(1.0) => "one",
+            Some(2.0) => "two",
+            _ => return Err(error("expected `part` to be 1 or 2")),
+        };
+
+        let default_name = format!("part_{part}");
+        let name = match field("name") {
+            Some(name) => name
+                .get::<String>()
+                .ok_or(error("expected `name` to be a string"))?,
+            None => &default_name,
+        };
+        // repeated names are numbered, e.g. `part_one_2`.
+        let mut unique = name.clone();
+        let mut count = 1;
+        while names.contains(&unique) {
+            count += 1;
+            unique = format!("{name}_{count}");
+        }
+        names.insert(unique.clone());
+        let name = unique;
+
+        let function = match field("function") {
+            Some(f) => f
+                .get::<String>()
+                .ok_or(error("expected `function` to be a string"))?
+                .clone(),
+            None => format!("part_{part}"),
+        };
+
+        let input = match (field("file"), field("input")) {
+            (None, None) => "advent_of_code::template::read_file(\"examples\", PUZZLE)".to_string(),
+            (Some(file), None) => format!(
+                "advent_of_code::template::read_example(PUZZLE, {:?})",
+                file.get::<String>()
+                    .ok_or(error("expected `file` to be a string"))?
+            ),
+            (None, Some(input)) => format!(
+                "String::from({:?})",
+                input
+                    .get::<String>()
+                    .ok_or(error("expected `input` to be a string"))?
+            ),
+            (Some(_), Some(_)) => return Err(error("expected only one of `file` and `input`")),
+        };
+
+        let mut args = vec![match parse {
+            Some(parse) => format!("&super::{parse}(&input)"),
+            None => "&input".to_string(),
+        }];
+        if let Some(extra) = field("args") {
+            let extra: &Vec<JsonValue> =
+                extra.get().ok_or(error("expected `args` to be an array"))?;
+            for arg in extra {
+                args.push(rust_literal(arg).map_err(|e| error(&e))?);
+            }
+        }
+
+        let answer = match field("answer") {
+            None | Some(JsonValue::Null) => "None".to_string(),
+            Some(JsonValue::String(s)) => format!("Some({s:?})"),
+            Some(JsonValue::Number(n)) => {
+                format!("Some({:?})", format_number(*n).map_err(|e| error(&e))?)
+            }
+            Some(_) => return Err(error("expected `answer` to be a string, number or null")),
+        };
+
+        // e.g. for examples that a solution does not handle yet.
+        let ignore = match field("ignore") {
+            None | Some(JsonValue::Boolean(false)) => String::new(),
+            Some(JsonValue::Boolean(true)) => "#[ignore]\n".to_string(),
+            Some(JsonValue::String(reason)) => format!("#[ignore = {reason:?}]\n"),
+            Some(_) => return Err(error("expected `ignore` to be a boolean or a reason")),
+        };
+
+        out.push_str(&format!(
+            "{ignore}#[test]\nfn {name}() {{\n    let input = {input};\n    \
+            advent_of_code::template::runner::assert_example(super::{function}({}), {answer});\n}}\n\n",
+            args.join(", ")
+        ));
+    }
+
+    Ok(out)
+}
+
+fn rust_literal(value: &JsonValue) -> Result<String, String> {
+    match value {
+        JsonValue::Number(n) => format_number(*n),
+        JsonValue::String(s) => Ok(format!("{s:?}")),
+        JsonValue::Boolean(b) => Ok(b.to_string()),
+        _ => Err("unsupported value in `args`".to_string()),
+    }
+}
+
+/// JSON numbers are floats, but the parameters and answers of puzzles are integers.
+/// Integers from 2^53 on cannot be represented exactly, so these answers have to be written as strings.
+fn format_number(n: f64) -> Result<String, String> {
+    if n.abs() >= (1_u64 << 53) as f64 {
+        Err(format!(
+            "`{n}` is too large to be represented exactly as a JSON number, write it as a string instead"
+        ))
+    } else if n.fract() == 0.0 {
+        Ok(format!("{}", n as i64))
+    } else {
+        Ok(n.to_string())
+    }
+}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(2024, 1);
 use std::collections::HashMap;
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -44,20 +44,3 @@ pub fn part_two(input: &str) -> Option<u32> {
     let sum = list1.iter().map(|n| n * hash2.get(n).unwrap_or(&0)).sum();
     Some(sum)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
-    }
-}
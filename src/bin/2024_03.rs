@@ -1,4 +1,4 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(2024, 3);
 use itertools::Itertools;
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -128,21 +128,3 @@ pub fn part_two(input: &str) -> Option<u32> {
     }
     Some(sum)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(161));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result =
-            part_two("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))");
-        assert_eq!(result, Some(48));
-    }
-}
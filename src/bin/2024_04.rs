@@ -1,4 +1,4 @@
-advent_of_code::solution!(4);
+advent_of_code::solution!(2024, 4);
 fn get_vec_from_input(input: &str) -> Vec<Vec<u8>> {
     input.lines().map(|line| line.bytes().collect()).collect()
 }
@@ -86,50 +86,3 @@ pub fn part_two(input: &str) -> Option<u32> {
     }
     Some(count)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one_small() {
-        let input = "..X...
-.SAMX.
-.A..A.
-XMAS.S
-.X....";
-        let result = part_one(&input);
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(18));
-    }
-
-    #[test]
-    fn test_part_two_small() {
-        let input = "M.S
-.A.
-M.S";
-        let result = part_two(&input);
-        assert_eq!(result, Some(1));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = ".M.S......
-..A..MSMS.
-.M.S.MAA..
-..A.ASMSM.
-.M.S.M....
-..........
-S.S.S.S.S.
-.A.A.A.A..
-M.M.M.M.M.
-..........";
-        let result = part_two(input);
-        assert_eq!(result, Some(9));
-    }
-}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(2024, 5);
 
 use std::collections::HashMap;
 
@@ -166,20 +166,3 @@ pub fn part_two(input: &str) -> Option<u32> {
     }
     Some(result)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
-    }
-}
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2024, 6);
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 enum Direction {
@@ -196,20 +196,3 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     Some(loop_count)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(41));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
-}
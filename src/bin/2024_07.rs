@@ -1,4 +1,4 @@
-advent_of_code::solution!(7);
+advent_of_code::solution!(2024, 7);
 
 fn parse_input(input: &str) -> Vec<(usize, Vec<usize>)> {
     input
@@ -81,20 +81,3 @@ pub fn part_two(input: &str) -> Option<usize> {
     }
     Some(total)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result: Option<usize> = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
-    }
-}
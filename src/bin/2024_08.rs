@@ -1,4 +1,4 @@
-advent_of_code::solution!(8);
+advent_of_code::solution!(2024, 8);
 
 use std::cmp;
 use std::collections::{HashMap, HashSet};
@@ -145,109 +145,3 @@ pub fn part_two(input: &str) -> Option<u32> {
     }
     Some(nodes.len() as u32)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
-    }
-
-    #[test]
-    fn test_part_one_1() {
-        let result = part_one(
-            "..........
-..........
-..........
-....a.....
-..........
-.....a....
-..........
-..........
-..........
-..........",
-        );
-        assert_eq!(result, Some(2));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(
-            "..........
-..........
-..........
-....a.....
-........a.
-.....a....
-..........
-..........
-..........
-..........",
-        );
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_one_3() {
-        let result = part_one(
-            "..........
-..........
-..........
-....a.....
-........a.
-.....a....
-..........
-.......A..
-..........
-..........",
-        );
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_one_4() {
-        let result = part_one(
-            "............
-........0...
-.....0......
-.......0....
-....0.......
-............
-............
-............
-............
-............
-............
-............",
-        );
-        assert_eq!(result, Some(10));
-    }
-
-    #[test]
-    fn test_part_one_5() {
-        let result = part_one(
-            "............
-............
-............
-............
-............
-......A.....
-............
-............
-........A...
-.........A..
-............
-............",
-        );
-        assert_eq!(result, Some(5));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(34));
-    }
-}
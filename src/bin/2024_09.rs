@@ -1,4 +1,4 @@
-advent_of_code::solution!(9);
+advent_of_code::solution!(2024, 9);
 use std::fmt;
 
 #[derive(Debug, Copy, Clone)]
@@ -102,9 +102,7 @@ pub fn part_one(input: &str) -> Option<u64> {
                         });
                         tail_cursor -= 1;
                     } else {
-                        let mut smaller_tail_file = tail_file;
                         let size_left = tail_file.size - head_empty.size;
-                        smaller_tail_file.size = head_empty.size;
                         compact_blocks.push(Block::F(File {
                             size: head_empty.size,
                             file_index: tail_file.file_index,
@@ -256,20 +254,3 @@ pub fn part_two(input: &str) -> Option<u64> {
     let checksum = get_checksum2(&original_blocks);
     Some(checksum)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1928));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2858));
-    }
-}
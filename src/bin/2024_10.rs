@@ -1,4 +1,4 @@
-advent_of_code::solution!(10);
+advent_of_code::solution!(2024, 10);
 
 use std::collections::{HashMap, HashSet};
 
@@ -204,60 +204,3 @@ pub fn part_two(input: &str) -> Option<u32> {
     }
     Some(total_paths)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(36));
-    }
-
-    #[test]
-    fn test_part_one_3() {
-        let result = part_one(
-            "...0...
-...1...
-...2...
-6543456
-7.....7
-8.....8
-9.....9",
-        );
-        assert_eq!(result, Some(2));
-    }
-    #[test]
-    fn test_part_one_4() {
-        let result = part_one(
-            "..90..9
-...1.98
-...2..7
-6543456
-765.987
-876....
-987....",
-        );
-        assert_eq!(result, Some(4));
-    }
-    #[test]
-    fn test_part_one_5() {
-        let result = part_one(
-            "10..9..
-2...8..
-3...7..
-4567654
-...8..3
-...9..2
-.....01",
-        );
-        assert_eq!(result, Some(3));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(81));
-    }
-}
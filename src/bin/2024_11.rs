@@ -1,4 +1,4 @@
-advent_of_code::solution!(11);
+advent_of_code::solution!(2024, 11);
 
 fn get_num_digits(num: &usize) -> usize {
     let mut num_digits = 0;
@@ -22,7 +22,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         for num in this_input.iter() {
             if *num == 0 {
                 new_input.push(1);
-            } else if get_num_digits(num) % 2 == 0 {
+            } else if get_num_digits(num).is_multiple_of(2) {
                 let printed_num = num.to_string();
                 let half = printed_num.len() / 2;
 
@@ -46,7 +46,7 @@ fn build_list_from_seed(seed: usize, depth: usize) -> Vec<usize> {
         for num in this_input.iter() {
             if *num == 0 {
                 new_input.push(1);
-            } else if get_num_digits(num) % 2 == 0 {
+            } else if get_num_digits(num).is_multiple_of(2) {
                 let printed_num = num.to_string();
                 let half = printed_num.len() / 2;
 
@@ -88,7 +88,7 @@ fn count_nodes_at_depth(
     let mut count = 0;
     if node.value == 0 {
         count += count_nodes_at_depth(&Node::new(1, node.depth - 1), count_cache);
-    } else if get_num_digits(&node.value) % 2 == 0 {
+    } else if get_num_digits(&node.value).is_multiple_of(2) {
         let printed_num = node.value.to_string();
         let half = printed_num.len() / 2;
 
@@ -130,20 +130,3 @@ pub fn part_two(input: &str) -> Option<usize> {
     let max_depth = 75;
     part_two_with_depth(input, max_depth)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
-    }
-
-    #[test]
-    fn test_part_two_with_depth() {
-        let result = part_two_with_depth(&advent_of_code::template::read_file("examples", DAY), 25);
-        assert_eq!(result, Some(55312));
-    }
-}
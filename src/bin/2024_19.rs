@@ -1,4 +1,4 @@
-advent_of_code::solution!(19);
+advent_of_code::solution!(2024, 19);
 
 //use rayon::prelude::*;
 use std::collections::HashMap;
@@ -81,38 +81,3 @@ pub fn part_two(input: &str) -> Option<usize> {
         None
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
-    #[test]
-    fn test_part_one_1() {
-        let result = part_one(
-            "r, wr, b, g, bwu, rb, gb, br
-
-brwrr",
-        );
-        assert_eq!(result, Some(1));
-    }
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(
-            "r, wr, b, g, bwu, rb, gb, br
-
-ubwu",
-        );
-        assert_eq!(result, None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(16));
-    }
-}
@@ -1,10 +1,11 @@
-advent_of_code::solution!(20);
+advent_of_code::solution!(2024, 20, parse = parse_input);
 
 use std::collections::HashMap;
 
 use itertools::Itertools;
 
-struct Maze {
+#[derive(Clone)]
+pub struct Maze {
     maze: Vec<Vec<u8>>,
     start: (usize, usize),
     end: (usize, usize),
@@ -81,12 +82,12 @@ fn get_steps_for_maze(maze: &Maze) -> Option<usize> {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    part_one_with_limit(input, 100)
+pub fn part_one(maze: &Maze) -> Option<usize> {
+    part_one_with_limit(maze, 100)
 }
 
-pub fn part_one_with_limit(input: &str, limit: usize) -> Option<usize> {
-    let mut maze = parse_input(input);
+pub fn part_one_with_limit(maze: &Maze, limit: usize) -> Option<usize> {
+    let mut maze = maze.clone();
 
     let max_y = maze.maze.len();
     let max_x = maze.maze[0].len();
@@ -165,11 +166,10 @@ fn get_list_of_nearby_spots(
     }
     spots
 }
-pub fn part_two(input: &str) -> Option<u32> {
-    part_two_ex(input, 20, 100)
+pub fn part_two(maze: &Maze) -> Option<u32> {
+    part_two_ex(maze, 20, 100)
 }
-pub fn part_two_ex(input: &str, shortcut_distance: i32, min_improvement: usize) -> Option<u32> {
-    let maze = parse_input(input);
+pub fn part_two_ex(maze: &Maze, shortcut_distance: i32, min_improvement: usize) -> Option<u32> {
     let mut maze_steps_for_each_position: HashMap<(usize, usize), usize> = HashMap::new();
     let mut route = Vec::new();
     let mut cursor = maze.start;
@@ -207,7 +207,7 @@ pub fn part_two_ex(input: &str, shortcut_distance: i32, min_improvement: usize)
 
     let mut shortcut_count: HashMap<usize, usize> = HashMap::new();
     for here in route.iter() {
-        get_list_of_nearby_spots(*here, shortcut_distance, &maze)
+        get_list_of_nearby_spots(*here, shortcut_distance, maze)
             .iter()
             .for_each(|shortcut| {
                 let steps_to_here = maze_steps_for_each_position[here];
@@ -259,21 +259,12 @@ pub fn part_two_ex(input: &str, shortcut_distance: i32, min_improvement: usize)
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one_with_limit(&advent_of_code::template::read_file("examples", DAY), 1);
-        assert_eq!(result, Some(44));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two_ex(&advent_of_code::template::read_file("examples", DAY), 6, 50);
-        assert_eq!(result, Some(46));
-    }
     #[test]
     #[ignore]
     fn test_part_two_real() {
-        let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "inputs", PUZZLE,
+        )));
         assert_eq!(result, Some(986082));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(21);
+advent_of_code::solution!(2024, 21);
 use std::collections::HashMap;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
@@ -338,7 +338,7 @@ fn robot_presses(
             // split input by A and recurse on each part
             input_count = dir_pushes.split_inclusive('A').map(|part| {
                 // compute count for each part at the next level
-                robot_presses(&part, robot_count - 1, cache)
+                robot_presses(part, robot_count - 1, cache)
             }).sum();
         }
         cache.insert((presses.to_owned(), robot_count), input_count);
@@ -368,112 +368,3 @@ pub fn part_two_ex(input: &str, robot_count: usize) -> Option<usize> {
     }
     Some(total_complexity)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(175396398527088));
-    }
-
-    #[test]
-    fn test_part_two_ex() {
-        let result = part_two_ex(&advent_of_code::template::read_file("examples", DAY), 2);
-        assert_eq!(result, Some(126384));
-    }
-
-    #[test]
-    fn test_part_two_029() {
-        let result = part_two_ex("029A", 2);
-        assert_eq!(
-            result,
-            Some("<vA<AA>>^AvAA<^A>A<v<A>>^AvA^A<vA>^A<v<A>^A>AAvA^A<v<A>A>^AAAvA<^A>A".len() * 29)
-        );
-    }
-    #[test]
-    fn test_part_two_980() {
-        let result = part_two_ex("980A", 2);
-        assert_eq!(
-            result,
-            Some("<v<A>>^AAAvA^A<vA<AA>>^AvAA<^A>A<v<A>A>^AAAvA<^A>A<vA>^A<A>A".len() * 980)
-        );
-    }
-    #[test]
-    fn test_part_two_179() {
-        let result = part_two_ex("179A", 2);
-        assert_eq!(
-            result,
-            Some(
-                "<v<A>>^A<vA<A>>^AAvAA<^A>A<v<A>>^AAvA^A<vA>^AA<A>A<v<A>A>^AAAvA<^A>A".len() * 179
-            )
-        );
-    }
-    #[test]
-    fn test_part_two_456() {
-        let result = part_two_ex("456A", 2);
-        assert_eq!(
-            result,
-            Some("<v<A>>^AA<vA<A>>^AAvAA<^A>A<vA>^A<A>A<vA>^A<A>A<v<A>A>^AAvA<^A>A".len() * 456)
-        );
-    }
-    #[test]
-    fn test_part_two_379() {
-        let result = part_two_ex("379A", 2);
-        assert_eq!(
-            result,
-            Some("<v<A>>^AvA^A<vA<AA>>^AAvA<^A>AAvA^A<vA>^AA<A>A<v<A>A>^AAAvA<^A>A".len() * 379)
-        );
-    }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(126384));
-    }
-
-    #[test]
-    fn test_part_one_029() {
-        let result = part_one("029A");
-        assert_eq!(
-            result,
-            Some("<vA<AA>>^AvAA<^A>A<v<A>>^AvA^A<vA>^A<v<A>^A>AAvA^A<v<A>A>^AAAvA<^A>A".len() * 29)
-        );
-    }
-    #[test]
-    fn test_part_one_980() {
-        let result = part_one("980A");
-        assert_eq!(
-            result,
-            Some("<v<A>>^AAAvA^A<vA<AA>>^AvAA<^A>A<v<A>A>^AAAvA<^A>A<vA>^A<A>A".len() * 980)
-        );
-    }
-    #[test]
-    fn test_part_one_179() {
-        let result = part_one("179A");
-        assert_eq!(
-            result,
-            Some(
-                "<v<A>>^A<vA<A>>^AAvAA<^A>A<v<A>>^AAvA^A<vA>^AA<A>A<v<A>A>^AAAvA<^A>A".len() * 179
-            )
-        );
-    }
-    #[test]
-    fn test_part_one_456() {
-        let result = part_one("456A");
-        assert_eq!(
-            result,
-            Some("<v<A>>^AA<vA<A>>^AAvAA<^A>A<vA>^A<A>A<vA>^A<A>A<v<A>A>^AAvA<^A>A".len() * 456)
-        );
-    }
-    #[test]
-    fn test_part_one_379() {
-        let result = part_one("379A");
-        assert_eq!(
-            result,
-            Some("<v<A>>^AvA^A<vA<AA>>^AAvA<^A>AAvA^A<vA>^AA<A>A<v<A>A>^AAAvA<^A>A".len() * 379)
-        );
-    }
-}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(23);
+advent_of_code::solution!(2024, 23);
 
 use itertools::Itertools;
 use petgraph::prelude::*;
@@ -62,7 +62,7 @@ pub fn part_two(input: &str) -> Option<String> {
                                 // sort the triples cannonically
                                 let mut nodes = vec![node]
                                     .into_iter()
-                                    .chain(neighbor_subset.into_iter())
+                                    .chain(neighbor_subset)
                                     .collect::<Vec<_>>();
                                 nodes.sort();
                                 Some(nodes)
@@ -79,25 +79,16 @@ pub fn part_two(input: &str) -> Option<String> {
             biggest_cluster = Some(cluster[0].clone());
         }
     }
-    if let Some(biggest_cluster) = biggest_cluster {
-        Some(biggest_cluster.join(",").to_string())
-    } else {
-        None
-    }
+    biggest_cluster.map(|biggest_cluster| biggest_cluster.join(",").to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
-    }
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(["co", "de", "ka", "ta"].join(",")));
     }
 }
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use petgraph::graphmap::DiGraphMap;
 
-advent_of_code::solution!(24);
+advent_of_code::solution!(2024, 24);
 
 enum Op {
     And(String, String, String),
@@ -112,8 +112,8 @@ pub fn part_two(input: &str) -> Option<String> {
     part_two_ex(input, 4)
 }
 
-pub fn part_two_ex(input: &str, swaps: u32) -> Option<String> {
-    let (mut wires, ops) = parse_input(input);
+pub fn part_two_ex(input: &str, _swaps: u32) -> Option<String> {
+    let (_wires, ops) = parse_input(input);
     let mut di_graph: DiGraphMap<&str, &str> = DiGraphMap::new();
     for op in ops.iter() {
         match op {
@@ -147,29 +147,6 @@ pub fn part_two_ex(input: &str, swaps: u32) -> Option<String> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one_small() {
-        let result = part_one(
-            "x00: 1
-x01: 1
-x02: 1
-y00: 0
-y01: 1
-y02: 0
-
-x00 AND y00 -> z00
-x01 XOR y01 -> z01
-x02 OR y02 -> z02",
-        );
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2024));
-    }
-
     #[test]
     #[ignore]
     fn test_part_two() {
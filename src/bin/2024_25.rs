@@ -1,6 +1,8 @@
-advent_of_code::solution!(25);
+advent_of_code::solution!(2024, 25);
 
-fn parse_input(input: &str) -> (Vec<(u8, u8, u8, u8, u8)>, Vec<(u8, u8, u8, u8, u8)>) {
+type Heights = (u8, u8, u8, u8, u8);
+
+fn parse_input(input: &str) -> (Vec<Heights>, Vec<Heights>) {
     let mut locks = Vec::new();
     let mut keys = Vec::new();
     for lock_key in input.split("\n\n") {
@@ -17,10 +19,10 @@ fn parse_input(input: &str) -> (Vec<(u8, u8, u8, u8, u8)>, Vec<(u8, u8, u8, u8,
             })
             .collect::<Vec<Vec<u8>>>();
 
-        let mut lock_key = vec![0; 5];
+        let mut lock_key = [0; 5];
         for col in 0..5 {
-            for row in 0..7 {
-                lock_key[col] += details[row][col];
+            for row in &details[..7] {
+                lock_key[col] += row[col];
             }
         }
         if details[0][0] == 0 {
@@ -54,8 +56,8 @@ pub fn part_one(input: &str) -> Option<usize> {
         println!("key: {:?}", key);
     }*/
     let mut possible_matches: usize = 0;
-    for (lock_num, lock) in locks.iter().enumerate() {
-        for (key_num, key) in keys.iter().enumerate() {
+    for lock in locks.iter() {
+        for key in keys.iter() {
             if (lock.0 + key.0) <= 7
                 && (lock.1 + key.1) <= 7
                 && (lock.2 + key.2) <= 7
@@ -74,23 +76,6 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(possible_matches)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(_input: &str) -> Option<u32> {
     None
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-}
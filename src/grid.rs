@@ -0,0 +1,74 @@
+/// A grid of bytes, e.g. a map in the puzzle input. Used by the `grid` scaffold template.
+/// Cells are addressed by `(x, y)`, where `(0, 0)` is the top left cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid {
+    pub cells: Vec<Vec<u8>>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Grid {
+    /// Parses one row of cells per line.
+    pub fn parse(input: &str) -> Self {
+        let cells: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+        Grid {
+            width: cells.first().map_or(0, Vec::len),
+            height: cells.len(),
+            cells,
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        self.cells.get(y)?.get(x).copied()
+    }
+
+    /// The positions of all cells with the given value.
+    pub fn find(&self, value: u8) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells.iter().enumerate().flat_map(move |(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(move |(_, cell)| **cell == value)
+                .map(move |(x, _)| (x, y))
+        })
+    }
+
+    /// The orthogonal neighbours of a cell, in the order up, right, down, left.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let x = x.checked_add_signed(dx)?;
+                let y = y.checked_add_signed(dy)?;
+                (x < self.width && y < self.height).then_some((x, y))
+            })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+
+    #[test]
+    fn parses_grids() {
+        let grid = Grid::parse("S.#\n..E\n");
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid.get(2, 0), Some(b'#'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.find(b'E').collect::<Vec<_>>(), vec![(2, 1)]);
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = Grid::parse("...\n...\n");
+        assert_eq!(
+            grid.neighbours(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours(1, 1).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 1), (0, 1)]
+        );
+    }
+}
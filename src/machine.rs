@@ -0,0 +1,92 @@
+use crate::template::{parse_at, ParseError};
+
+/// A machine with registers that runs a program of numbers. Used by the `vm` scaffold template.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Machine {
+    pub registers: Vec<i64>,
+    pub program: Vec<i64>,
+    /// The index of the next instruction in the program.
+    pub pc: usize,
+    pub output: Vec<i64>,
+}
+
+impl Machine {
+    /// Parses lines like `Register A: 729` and `Program: 0,1,5,4,3,0`. Other lines are ignored.
+    pub fn try_parse(input: &str) -> Result<Self, ParseError> {
+        let mut machine = Machine::default();
+
+        for line in input.lines() {
+            if let Some((_, value)) = line
+                .strip_prefix("Register ")
+                .and_then(|l| l.split_once(": "))
+            {
+                machine.registers.push(parse_at(input, value)?);
+            } else if let Some(values) = line.strip_prefix("Program: ") {
+                machine.program = values
+                    .split(',')
+                    .map(|value| parse_at(input, value))
+                    .collect::<Result<_, _>>()?;
+            }
+        }
+
+        Ok(machine)
+    }
+
+    /// Like [`Machine::try_parse`], but panics on invalid input with its position,
+    /// e.g. `line 2, column 13: invalid value ...`.
+    pub fn parse(input: &str) -> Self {
+        Machine::try_parse(input).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Runs the program until the program counter leaves it. `step` executes the instruction with the given opcode
+    /// at the program counter, and returns the program counter of the next instruction, e.g. `machine.pc + 2`.
+    pub fn run(&mut self, mut step: impl FnMut(&mut Machine, i64) -> usize) {
+        while let Some(&opcode) = self.program.get(self.pc) {
+            self.pc = step(self, opcode);
+        }
+    }
+
+    /// The output, separated by commas.
+    pub fn output_string(&self) -> String {
+        self.output
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Machine;
+
+    #[test]
+    fn parses_machines() {
+        let machine = Machine::parse(
+            "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0\n",
+        );
+        assert_eq!(machine.registers, vec![729, 0, 0]);
+        assert_eq!(machine.program, vec![0, 1, 5, 4, 3, 0]);
+        assert_eq!(machine.pc, 0);
+
+        let error = Machine::try_parse("Register A: 0\nRegister B: 7x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 13));
+    }
+
+    #[test]
+    fn runs_programs() {
+        // 0: add the operand to register A, 1: output register A.
+        let mut machine = Machine::parse("Register A: 1\n\nProgram: 0,2,1,0,0,3,1,0\n");
+        machine.run(|machine, opcode| {
+            let operand = machine.program[machine.pc + 1];
+            match opcode {
+                0 => machine.registers[0] += operand,
+                _ => machine.output.push(machine.registers[0]),
+            }
+            machine.pc + 2
+        });
+        assert_eq!(machine.output_string(), "3,6");
+    }
+}
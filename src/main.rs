@@ -1,81 +1,341 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, export, harvest, read, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
+use advent_of_code::template::runner::Solution;
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, PuzzleId, Year};
 #[cfg(feature = "today")]
 use std::process;
 
+/// All solutions, compiled into this binary. See `build.rs`.
+#[cfg(all(feature = "in-process", not(test)))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+/// Without the `in-process` feature, every solution is run in a separate binary.
+#[cfg(all(feature = "in-process", not(test)))]
+const SOLUTIONS: Option<&[Solution]> = Some(solutions::SOLUTIONS);
+#[cfg(not(all(feature = "in-process", not(test))))]
+const SOLUTIONS: Option<&[Solution]> = None;
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        parse_day_set, runner::RunOptions, Baseline, Comparison, Day, DayFilters, ExportFormat,
+        InputSource, Limits, PuzzleId, ScaffoldTemplate, Year,
+    };
+    use std::{collections::HashSet, env, process};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            force: bool,
+        },
+        Harvest {
+            year: Year,
+            days: Option<HashSet<Day>>,
+        },
+        Export {
+            format: ExportFormat,
+            output: Option<String>,
+            history: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
+            template: ScaffoldTemplate,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            part: Option<u8>,
+            limits: Limits,
+            source: InputSource,
         },
         All {
+            year: Year,
+            days: Option<HashSet<Day>>,
+            filters: DayFilters,
             release: bool,
+            limits: Limits,
         },
         Time {
+            year: Year,
             all: bool,
-            day: Option<Day>,
+            days: Option<HashSet<Day>>,
+            filters: DayFilters,
             store: bool,
+            force: bool,
+            options: RunOptions,
+            comparison: Option<Comparison>,
+        },
+        Verify {
+            year: Year,
+            days: Option<HashSet<Day>>,
+            filters: DayFilters,
+            release: bool,
+            limits: Limits,
+            all_inputs: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            template: ScaffoldTemplate,
+        },
+    }
+
+    /// Parses the day argument of a command, which has to be part of the calendar of `year`.
+    fn puzzle(
+        year: Year,
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let day: String = args.free_from_str()?;
+        Ok(PuzzleId::new(year, Day::from_str_in_year(&day, year)?))
+    }
+
+    /// Parses the status filters of `all`, `time` and `verify`.
+    fn filters(args: &mut pico_args::Arguments) -> DayFilters {
+        DayFilters {
+            unsolved: args.contains("--unsolved"),
+            slow: args.contains("--slow"),
+        }
+    }
+
+    /// Parses the optional day set of `all`, `time`, `verify` and `harvest`, e.g. `1-5,7,20-`.
+    /// Has to be parsed after all flags, as it is a free argument.
+    fn days(
+        year: Year,
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<HashSet<Day>>, Box<dyn std::error::Error>> {
+        let days: Option<String> = args.opt_free_from_str()?;
+        Ok(days.map(|days| parse_day_set(&days, year)).transpose()?)
+    }
+
+    /// Parses the part that `solve` and `time` run. Defaults to both parts.
+    fn part(args: &mut pico_args::Arguments) -> Result<Option<u8>, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--part")? {
+            Some(part @ (1 | 2)) => Ok(Some(part)),
+            Some(_) => Err("expecting `--part` to be 1 or 2.".into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses the template of `scaffold`. Flags take precedence over `AOC_TEMPLATE` and `AOC_ANSWER_TYPE`.
+    fn template(
+        args: &mut pico_args::Arguments,
+    ) -> Result<ScaffoldTemplate, Box<dyn std::error::Error>> {
+        let name: Option<String> = args.opt_value_from_str("--template")?;
+        let name = name
+            .or_else(|| env::var("AOC_TEMPLATE").ok())
+            .unwrap_or_else(|| "default".to_string());
+        let answer_type: Option<String> = args.opt_value_from_str("--answer-type")?;
+
+        let mut template = ScaffoldTemplate::load(&name)?;
+        if let Some(answer_type) = answer_type.or_else(|| env::var("AOC_ANSWER_TYPE").ok()) {
+            template.answer_type = answer_type;
+        }
+        Ok(template)
+    }
+
+    /// Parses the limits of the parts of a solution. Flags take precedence over the environment.
+    fn limits(args: &mut pico_args::Arguments) -> Result<Limits, Box<dyn std::error::Error>> {
+        let defaults = Limits::from_env();
+        Ok(Limits {
+            timeout: args
+                .opt_value_from_fn("--timeout", Limits::parse_timeout)?
+                .or(defaults.timeout),
+            memory: args
+                .opt_value_from_fn("--memory-limit", Limits::parse_memory)?
+                .or(defaults.memory),
+        })
+    }
+
+    /// Parses the baseline and threshold of `time --compare`.
+    fn comparison(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Comparison>, Box<dyn std::error::Error>> {
+        let baseline = args.opt_value_from_fn("--compare", Baseline::parse)?;
+        let threshold = args.opt_value_from_fn("--threshold", Comparison::parse_threshold)?;
+        match (baseline, threshold) {
+            (Some(baseline), threshold) => Ok(Some(Comparison::new(baseline, threshold))),
+            (None, Some(_)) => Err("`--threshold` can only be passed with `--compare`.".into()),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Parses the input source of `solve`. Defaults to the puzzle input.
+    fn input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, Box<dyn std::error::Error>> {
+        let path: Option<String> = args.opt_value_from_str("--input")?;
+        let example_part: Option<u8> = args.opt_value_from_str("--example-part")?;
+        let example_file: Option<String> = args.opt_value_from_str("--example-file")?;
+        let is_example =
+            args.contains("--example") || example_part.is_some() || example_file.is_some();
+        let is_stdin = args.contains("--stdin");
+
+        match (path, is_example, is_stdin) {
+            (None, false, false) => Ok(InputSource::Puzzle),
+            (Some(path), false, false) => Ok(InputSource::File(path)),
+            (None, true, false) => match (example_part, example_file) {
+                (part, None) => Ok(InputSource::Example(part)),
+                (None, Some(file)) => Ok(InputSource::ExampleFile(file)),
+                (Some(_), Some(_)) => {
+                    Err("only one of `--example-part` and `--example-file` can be passed.".into())
+                }
+            },
+            (None, false, true) => Ok(InputSource::Stdin),
+            _ => Err("only one of `--input`, `--example` and `--stdin` can be passed.".into()),
+        }
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let subcommand = args.subcommand()?;
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+        // every command works on a single year, which defaults to `AOC_YEAR`.
+        let year: Option<Year> = args.opt_value_from_str("--year")?.or_else(Year::from_env);
+        let year = || year.ok_or("no year given. Pass `--year` or set `AOC_YEAR`.");
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let year = year()?;
+                let release = args.contains("--release");
+                let filters = filters(&mut args);
+                let limits = limits(&mut args)?;
+
+                AppArguments::All {
+                    year,
+                    days: days(year, &mut args)?,
+                    filters,
+                    release,
+                    limits,
+                }
+            }
             Some("time") => {
+                let year = year()?;
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let force = args.contains("--force");
+                let filters = filters(&mut args);
+                let options = RunOptions {
+                    is_profiled: args.contains("--memory"),
+                    part: part(&mut args)?,
+                    limits: limits(&mut args)?,
+                    ..RunOptions::default()
+                };
+                let comparison = comparison(&mut args)?;
 
                 AppArguments::Time {
+                    year,
                     all,
-                    day: args.opt_free_from_str()?,
+                    days: days(year, &mut args)?,
+                    filters,
                     store,
+                    force,
+                    options,
+                    comparison,
+                }
+            }
+            Some("verify") => {
+                let year = year()?;
+                let release = args.contains("--release");
+                let all_inputs = args.contains("--all-inputs");
+                let filters = filters(&mut args);
+                let limits = limits(&mut args)?;
+
+                AppArguments::Verify {
+                    year,
+                    days: days(year, &mut args)?,
+                    filters,
+                    release,
+                    limits,
+                    all_inputs,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: puzzle(year()?, &mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: puzzle(year()?, &mut args)?,
             },
+            Some("examples") => {
+                let force = args.contains("--force");
+                AppArguments::Examples {
+                    puzzle: puzzle(year()?, &mut args)?,
+                    force,
+                }
+            }
+            Some("harvest") => {
+                let year = year()?;
+                AppArguments::Harvest {
+                    year,
+                    days: days(year, &mut args)?,
+                }
+            }
+            Some("export") => {
+                let output: Option<String> = args.opt_value_from_str("--output")?;
+                let format = match args.opt_value_from_str("--format")? {
+                    Some(format) => format,
+                    // e.g. `--output benchmarks.csv`.
+                    None => output.as_deref().and_then(ExportFormat::from_path).ok_or(
+                        "no format given. Pass `--format` with one of csv, md, html and svg.",
+                    )?,
+                };
+
+                let history = args.contains("--history");
+                if history && format != ExportFormat::Csv {
+                    return Err("`--history` can only be exported with `--format csv`.".into());
+                }
+
+                AppArguments::Export {
+                    format,
+                    output,
+                    history,
+                }
+            }
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: puzzle(year()?, &mut args)?,
+                template: template(&mut args)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let puzzle = puzzle(year()?, &mut args)?;
+                let submit: Option<u8> = args.opt_value_from_str("--submit")?;
+                let part = part(&mut args)?;
+
+                if submit
+                    .zip(part)
+                    .is_some_and(|(submit, part)| submit != part)
+                {
+                    return Err("cannot submit a part that is not selected with `--part`.".into());
+                }
+
+                AppArguments::Solve {
+                    puzzle,
+                    release: args.contains("--release"),
+                    submit,
+                    part,
+                    dhat: args.contains("--dhat"),
+                    limits: limits(&mut args)?,
+                    source: input_source(&mut args)?,
+                }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                template: template(&mut args)?,
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -102,38 +362,80 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                days,
+                filters,
+                release,
+                limits,
+            } => all::handle(year, days, filters, release, limits, SOLUTIONS),
+            AppArguments::Time {
+                year,
+                days,
+                filters,
+                all,
+                store,
+                force,
+                options,
+                comparison,
+            } => time::handle(
+                year, days, filters, all, store, force, options, comparison, SOLUTIONS,
+            ),
+            AppArguments::Verify {
+                year,
+                days,
+                filters,
+                release,
+                limits,
+                all_inputs,
+            } => verify::handle(year, days, filters, release, limits, all_inputs, SOLUTIONS),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples { puzzle, force } => examples::handle(puzzle, force),
+            AppArguments::Harvest { year, days } => harvest::handle(year, days),
+            AppArguments::Export {
+                format,
+                output,
+                history,
+            } => export::handle(format, output, history),
             AppArguments::Scaffold {
-                day,
+                puzzle,
+                template,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                // downloading first fills in the title of the puzzle.
+                if download {
+                    download::handle(puzzle);
+                }
+                scaffold::handle(puzzle, &template, overwrite);
                 if download {
-                    download::handle(day);
+                    examples::handle(puzzle, false);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                part,
+                limits,
+                source,
+            } => solve::handle(puzzle, release, dhat, submit, part, limits, &source),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+            AppArguments::Today { template } => {
+                match Year::today().zip(Day::today()) {
+                    Some((year, day)) => {
+                        let puzzle = PuzzleId::new(year, day);
+                        download::handle(puzzle);
+                        scaffold::handle(puzzle, &template, false);
+                        examples::handle(puzzle, false);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run during the advent calendar \
+                            of the current year. Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
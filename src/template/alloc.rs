@@ -0,0 +1,158 @@
+//! A global allocator that keeps track of the heap usage of the program.
+//! The runner uses it to enforce memory limits of solution parts, and to profile their heap usage.
+//! `solution!` installs it with the `count-alloc` feature, which the commands turn on for `--memory` and memory limits.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::template::timings::HeapStats;
+
+/// Wraps the system allocator, counting the bytes that are currently allocated.
+pub struct CountingAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+/// Number of allocations and bytes allocated since the program started. A reallocation counts as a new allocation.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+/// Heap usage in bytes above which allocations fail, see [`set_limit`].
+static LIMIT: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// The start of the message that the standard library prints before it aborts the process, when an allocation fails.
+pub const ALLOC_ERROR_MESSAGE: &str = "memory allocation of ";
+
+// the solutions that are compiled into the main binary do not install it, see `solution!`.
+#[cfg(test)]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+/// Whether heap usage is counted. This is not the case without the `count-alloc` feature,
+/// when profiling with `dhat`, or in the main binary.
+pub const IS_COUNTING: bool = cfg!(any(
+    test,
+    all(
+        feature = "count-alloc",
+        not(feature = "dhat-heap"),
+        not(feature = "in-process")
+    )
+));
+
+/// Whether an allocation of `size` bytes would exceed the memory limit.
+fn exceeds_limit(size: usize) -> bool {
+    CURRENT.load(Ordering::Relaxed).saturating_add(size) > LIMIT.load(Ordering::Relaxed)
+}
+
+fn count(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED.fetch_add(size, Ordering::Relaxed);
+}
+
+fn grow(size: usize) {
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn shrink(size: usize) {
+    CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if exceeds_limit(layout.size()) {
+            return ptr::null_mut();
+        }
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            count(layout.size());
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if exceeds_limit(layout.size()) {
+            return ptr::null_mut();
+        }
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(layout.size());
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if exceeds_limit(new_size.saturating_sub(layout.size())) {
+            return ptr::null_mut();
+        }
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            count(new_size);
+            if new_size > layout.size() {
+                grow(new_size - layout.size());
+            } else {
+                shrink(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Resets the peak heap usage to the current usage, and returns it.
+pub fn reset_peak() -> usize {
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    current
+}
+
+/// Limits the heap usage to `memory` bytes above the current usage, or removes the limit.
+/// Allocations that would exceed it fail, which aborts the process with [`ALLOC_ERROR_MESSAGE`].
+pub fn set_limit(memory: Option<u64>) {
+    let limit = memory.map_or(usize::MAX, |memory| {
+        let memory = usize::try_from(memory).unwrap_or(usize::MAX);
+        CURRENT.load(Ordering::Relaxed).saturating_add(memory)
+    });
+    LIMIT.store(limit, Ordering::Relaxed);
+}
+
+/// The peak heap usage in bytes since the last call to [`reset_peak`].
+pub fn peak() -> usize {
+    PEAK.load(Ordering::Relaxed)
+}
+
+/// Profiles the heap usage of a section of the program, e.g. a single run of a solution part.
+/// Allocations of other threads that run at the same time are counted as well.
+pub struct HeapProfiler {
+    baseline: usize,
+    allocations: usize,
+    allocated: usize,
+}
+
+impl HeapProfiler {
+    pub fn start() -> Self {
+        HeapProfiler {
+            baseline: reset_peak(),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated: ALLOCATED.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The heap usage since the profiler was started. The peak is relative to the usage at that time.
+    pub fn finish(&self) -> HeapStats {
+        let since = |counter: &AtomicUsize, start: usize| {
+            counter.load(Ordering::Relaxed).saturating_sub(start) as u64
+        };
+
+        HeapStats {
+            peak_bytes: peak().saturating_sub(self.baseline) as u64,
+            allocations: since(&ALLOCATIONS, self.allocations),
+            allocated_bytes: since(&ALLOCATED, self.allocated),
+        }
+    }
+}
@@ -0,0 +1,511 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client::Verdict, Day, PuzzleId, Year};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// A single answer that was submitted, together with the verdict of the server.
+#[derive(Clone, Debug)]
+pub struct Attempt {
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Represents all submissions for one part of a puzzle with a specific input.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    /// Fingerprint of the input the answer was computed for.
+    pub input: String,
+    pub attempts: Vec<Attempt>,
+}
+
+/// A ledger of all submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// Reasons to not send an answer to the server.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadySolved(String),
+    KnownWrong(Verdict),
+    AboveTooHigh(String),
+    BelowTooLow(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadySolved(answer) if answer.is_empty() => {
+                write!(f, "this part is already solved.")
+            }
+            Rejection::AlreadySolved(answer) => {
+                write!(f, "this part is already solved with answer `{answer}`.")
+            }
+            Rejection::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted and was {verdict}.")
+            }
+            Rejection::AboveTooHigh(bound) => {
+                write!(f, "this answer is not below `{bound}`, which was too high.")
+            }
+            Rejection::BelowTooLow(bound) => {
+                write!(f, "this answer is not above `{bound}`, which was too low.")
+            }
+        }
+    }
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns an empty ledger.
+    /// A ledger that cannot be read is an error, as storing over it would lose the recorded submissions.
+    pub fn read_from_file() -> Result<Self, String> {
+        Answers::read_from_path(ANSWERS_FILE_PATH)
+    }
+
+    fn read_from_path(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Answers::try_from(s),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+        .map_err(|e| format!("Failed to read answer ledger \"{path}\": {e}"))
+    }
+
+    pub fn get(&self, puzzle: PuzzleId, part: u8, input: &str) -> Option<&Answer> {
+        self.data
+            .iter()
+            .find(|a| a.puzzle == puzzle && a.part == part && a.input == input)
+    }
+
+    /// Returns the answer the server accepted for a part, if any.
+    pub fn correct_answer(&self, puzzle: PuzzleId, part: u8, input: &str) -> Option<&str> {
+        self.get(puzzle, part, input)?
+            .attempts
+            .iter()
+            .find(|a| a.verdict == Verdict::Correct)
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Checks whether an answer is worth sending to the server, based on previous verdicts.
+    pub fn check(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        input: &str,
+        answer: &str,
+    ) -> Result<(), Rejection> {
+        let Some(entry) = self.get(puzzle, part, input) else {
+            return Ok(());
+        };
+
+        for attempt in &entry.attempts {
+            match attempt.verdict {
+                Verdict::Correct => {
+                    return Err(Rejection::AlreadySolved(attempt.answer.clone()));
+                }
+                Verdict::AlreadySolved => {
+                    return Err(Rejection::AlreadySolved(String::new()));
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(attempt) = entry.attempts.iter().find(|a| {
+            a.answer == answer
+                && matches!(
+                    a.verdict,
+                    Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+                )
+        }) {
+            return Err(Rejection::KnownWrong(attempt.verdict));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        for attempt in &entry.attempts {
+            let Ok(bound) = attempt.answer.parse::<i128>() else {
+                continue;
+            };
+            match attempt.verdict {
+                Verdict::TooHigh if value >= bound => {
+                    return Err(Rejection::AboveTooHigh(attempt.answer.clone()));
+                }
+                Verdict::TooLow if value <= bound => {
+                    return Err(Rejection::BelowTooLow(attempt.answer.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the verdict for a submitted answer.
+    pub fn record(
+        &mut self,
+        puzzle: PuzzleId,
+        part: u8,
+        input: &str,
+        answer: &str,
+        verdict: Verdict,
+    ) {
+        let attempt = Attempt {
+            answer: answer.to_string(),
+            verdict,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        };
+
+        match self
+            .data
+            .iter_mut()
+            .find(|a| a.puzzle == puzzle && a.part == part && a.input == input)
+        {
+            Some(entry) => entry.attempts.push(attempt),
+            None => {
+                self.data.push(Answer {
+                    puzzle,
+                    part,
+                    input: input.to_string(),
+                    attempts: vec![attempt],
+                });
+                self.data.sort_by_key(|a| (a.puzzle, a.part));
+            }
+        }
+    }
+}
+
+/// Extracts the accepted answers from a downloaded puzzle description, in the order of the parts.
+/// Every solved part is followed by a paragraph like "Your puzzle answer was `1189304`."
+pub fn accepted_answers(markdown: &str) -> Vec<String> {
+    markdown
+        .split("Your puzzle answer was")
+        .skip(1)
+        .filter_map(|rest| {
+            let sentence = rest.split_once('\n').map_or(rest, |(line, _)| line);
+            let answer = match sentence.split('`').nth(1) {
+                Some(answer) => answer,
+                None => sentence.trim().trim_end_matches('.'),
+            };
+            Some(answer.trim().to_string()).filter(|a| !a.is_empty())
+        })
+        .collect()
+}
+
+/// Computes a stable fingerprint for an input (64-bit FNV-1a, as hex).
+pub fn fingerprint(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "too high" => Ok(Verdict::TooHigh),
+            "too low" => Ok(Verdict::TooLow),
+            "rate-limited" => Ok(Verdict::RateLimited),
+            "already solved" => Ok(Verdict::AlreadySolved),
+            s => Err(format!("Unknown verdict `{s}`.")),
+        }
+    }
+}
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year.to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day.to_string()),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("input".into(), JsonValue::String(value.input.clone()));
+        map.insert(
+            "attempts".into(),
+            JsonValue::Array(value.attempts.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        // ledgers written before multi-year support belong to the configured year.
+        let year = match json.get("year") {
+            None => Year::from_env(),
+            Some(v) => v.get::<String>().and_then(|year| Year::from_str(year).ok()),
+        }
+        .ok_or("Expected answer.year to be a Year struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .filter(|part| *part == 1 || *part == 2)
+            .ok_or("Expected answer.part to be 1 or 2.")?;
+
+        let input = json
+            .get("input")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.input to be a string.")?;
+
+        let attempts = json
+            .get("attempts")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected answer.attempts to be an array.")?;
+
+        Ok(Answer {
+            puzzle: PuzzleId::new(year, day),
+            part,
+            input: input.clone(),
+            attempts: attempts
+                .iter()
+                .map(Attempt::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Attempt> for JsonValue {
+    fn from(value: &Attempt) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Attempt {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected attempt to be a JSON object.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected attempt.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected attempt.verdict to be a string.")?
+            .parse()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|t| *t as u64)
+            .ok_or("Expected attempt.timestamp to be a number.")?;
+
+        Ok(Attempt {
+            answer: answer.clone(),
+            verdict,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{accepted_answers, Answers, Rejection};
+    use crate::{
+        day,
+        template::{aoc_client::Verdict, PuzzleId},
+        year,
+    };
+
+    const DAY_1: PuzzleId = PuzzleId::new(year!(2024), day!(1));
+    const DAY_2: PuzzleId = PuzzleId::new(year!(2024), day!(2));
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.record(DAY_1, 1, "abc", "100", Verdict::TooHigh);
+        answers.record(DAY_1, 1, "abc", "10", Verdict::TooLow);
+        answers.record(DAY_1, 1, "abc", "42", Verdict::Incorrect);
+        answers.record(DAY_2, 1, "abc", "7", Verdict::Correct);
+        answers
+    }
+
+    #[test]
+    fn allows_unknown_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.check(DAY_1, 1, "abc", "50"), Ok(()));
+        assert_eq!(answers.check(DAY_1, 2, "abc", "42"), Ok(()));
+        assert_eq!(answers.check(DAY_1, 1, "other", "42"), Ok(()));
+        assert_eq!(
+            answers.check(PuzzleId::new(year!(2023), day!(1)), 1, "abc", "42"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_known_wrong_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(DAY_1, 1, "abc", "42"),
+            Err(Rejection::KnownWrong(Verdict::Incorrect))
+        );
+    }
+
+    #[test]
+    fn rejects_answers_out_of_bounds() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(DAY_1, 1, "abc", "101"),
+            Err(Rejection::AboveTooHigh("100".into()))
+        );
+        assert_eq!(
+            answers.check(DAY_1, 1, "abc", "3"),
+            Err(Rejection::BelowTooLow("10".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_solved_parts() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(DAY_2, 1, "abc", "8"),
+            Err(Rejection::AlreadySolved("7".into()))
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = get_mock_answers();
+        let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].attempts.len(), 3);
+        assert_eq!(answers.data[0].attempts[0].verdict, Verdict::TooHigh);
+        assert_eq!(answers.data[1].attempts[0].answer, "7");
+    }
+
+    #[test]
+    fn refuses_unreadable_ledgers() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        assert_eq!(Answers::read_from_path(path).unwrap().data.len(), 0);
+
+        std::fs::write(path, "{ \"data\": [").unwrap();
+        let error = Answers::read_from_path(path).unwrap_err();
+        std::fs::remove_file(path).unwrap();
+
+        assert!(error.starts_with("Failed to read answer ledger"));
+    }
+
+    #[test]
+    fn extracts_accepted_answers() {
+        let markdown =
+            "## --- Day 1 ---\n\nWhat is the distance?\n\nYour puzzle answer was `1189304`.\n\n\
+            ## --- Part Two ---\n\nWhat is the score?\n\nYour puzzle answer was 24349736.\n";
+        assert_eq!(accepted_answers(markdown), vec!["1189304", "24349736"]);
+        assert!(accepted_answers("## --- Day 1 ---\n\nWhat is the distance?\n").is_empty());
+    }
+}
@@ -0,0 +1,630 @@
+/// Native client for the Advent of Code website.
+/// Fetches inputs and puzzle descriptions and submits answers on behalf of the user.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::PuzzleId;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/djrobson/aoc24_rust";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    BadSession,
+    NotUnlocked,
+    RateLimited,
+    Network(String),
+    UnexpectedResponse(u16),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::BadSession => {
+                write!(f, "the session cookie was rejected. It might have expired.")
+            }
+            AocClientError::NotUnlocked => write!(f, "this puzzle has not been unlocked yet."),
+            AocClientError::RateLimited => write!(f, "rate limited by the server."),
+            AocClientError::Network(e) => write!(f, "network request failed: {e}"),
+            AocClientError::UnexpectedResponse(status) => {
+                write!(f, "server responded with unexpected status {status}.")
+            }
+            AocClientError::IO(e) => write!(f, "could not write file: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/// The verdict the server gave for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    AlreadySolved,
+    /// The response was not recognised, e.g. an empty page. It is not recorded in the answer ledger.
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::RateLimited => "rate-limited",
+            Verdict::AlreadySolved => "already solved",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+/// The server response to an answer submission.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub verdict: Verdict,
+    /// The response message, converted to plain text.
+    pub message: String,
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    /// Creates a client from the environment.
+    /// The base url can be overridden with `AOC_BASE_URL`, e.g. to test against a local server.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Fetches the input of a puzzle.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", puzzle_url(puzzle)))
+    }
+
+    /// Fetches the page of a puzzle and converts its description to markdown.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&puzzle_url(puzzle))?;
+        Ok(puzzle_to_markdown(&html))
+    }
+
+    /// Submits an answer for one part of a puzzle.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<Submission, AocClientError> {
+        let url = self.url(&format!("{}/answer", puzzle_url(puzzle)));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        let html = read_response(response)?;
+        Ok(parse_submission(&html))
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.url(path))
+            .set("Cookie", &self.cookie())
+            .call();
+        read_response(response)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn puzzle_url(puzzle: PuzzleId) -> String {
+    format!("/{}/day/{}", puzzle.year, puzzle.day.into_inner())
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| AocClientError::Network(e.to_string())),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(match status {
+                400 | 401 | 403 => AocClientError::BadSession,
+                404 if body.contains("before it unlocks") => AocClientError::NotUnlocked,
+                429 => AocClientError::RateLimited,
+                // an expired session is redirected to an error page instead of the login.
+                500 if body.to_lowercase().contains("log in") => AocClientError::BadSession,
+                status => AocClientError::UnexpectedResponse(status),
+            })
+        }
+        Err(ureq::Error::Transport(e)) => Err(AocClientError::Network(e.to_string())),
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session);
+        }
+    }
+
+    let path = match env::var("AOC_SESSION_FILE") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => home_dir()?.join(SESSION_FILE_NAME),
+    };
+
+    fs::read_to_string(path)
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Fetches the input and description of a puzzle and writes them to the data directory.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = puzzle.input_path();
+    let puzzle_path = puzzle.puzzle_path();
+
+    let input = client.input(puzzle)?;
+    write_data_file(&input_path, &input)?;
+    let description = client.puzzle(puzzle)?;
+    write_data_file(&puzzle_path, &description)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", puzzle_path);
+    Ok(())
+}
+
+/// Fetches the description of a puzzle, writes it to the data directory and prints it.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let description = client.puzzle(puzzle)?;
+    write_data_file(&puzzle.puzzle_path(), &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<Submission, AocClientError> {
+    AocClient::from_env()?.submit(puzzle, part, result)
+}
+
+/// Writes a file, creating the year-scoped data directory if it does not exist yet.
+fn write_data_file(path: &str, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_submission(html: &str) -> Submission {
+    let message = collect_blocks(html, &["article"])
+        .first()
+        .map(|article| html_to_markdown(article).trim().to_string())
+        .unwrap_or_default();
+
+    let verdict = if message.contains("That's the right answer") {
+        Verdict::Correct
+    } else if message.contains("You gave an answer too recently") {
+        Verdict::RateLimited
+    } else if message.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if message.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if message.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else if message.contains("That's not the right answer") {
+        Verdict::Incorrect
+    } else {
+        Verdict::Unknown
+    };
+
+    Submission { verdict, message }
+}
+
+/// Converts the descriptions of a puzzle page to markdown.
+/// Keeps the "Your puzzle answer was" paragraphs of solved parts.
+fn puzzle_to_markdown(html: &str) -> String {
+    collect_blocks(html, &["article", "p"])
+        .iter()
+        .filter(|block| block.starts_with("<article") || block.contains("Your puzzle answer was"))
+        .map(|block| html_to_markdown(block).trim().to_string())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n"
+}
+
+/// Collects all top-level elements with one of the given tag names, in document order.
+fn collect_blocks<'a>(html: &'a str, tags: &[&str]) -> Vec<&'a str> {
+    let mut blocks = vec![];
+    let mut pos = 0;
+
+    while let Some(offset) = html[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &html[start + 1..];
+        let tag = tags.iter().find(|tag| {
+            rest.starts_with(**tag)
+                && rest[tag.len()..].starts_with(|c: char| c == '>' || c.is_whitespace())
+        });
+
+        match tag {
+            Some(tag) => {
+                let close = format!("</{tag}>");
+                let end = html[start..]
+                    .find(&close)
+                    .map_or(html.len(), |i| start + i + close.len());
+                blocks.push(&html[start..end]);
+                pos = end;
+            }
+            None => pos = start + 1,
+        }
+    }
+
+    blocks
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn get_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!("{name}=\"");
+    let start = tag.find(&needle)? + needle.len();
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+/// A minimal HTML to markdown converter for the subset of tags used in puzzle descriptions.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut pre_depth = 0;
+    let mut code_depth = 0;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, pre_depth > 0);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], pre_depth > 0);
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                pre_depth += 1;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                pre_depth -= 1;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if pre_depth > 0 => {}
+            ("code", false) => {
+                code_depth += 1;
+                out.push('`');
+            }
+            ("code", true) => {
+                code_depth -= 1;
+                out.push('`');
+            }
+            ("em", _) if pre_depth == 0 && code_depth == 0 => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("ul", true) => out.push('\n'),
+            ("a", false) => {
+                out.push('[');
+                links.push(get_attribute(tag, "href").unwrap_or_default().to_string());
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    out
+}
+
+fn push_text(out: &mut String, text: &str, is_preformatted: bool) {
+    let text = decode_entities(text);
+
+    if is_preformatted {
+        out.push_str(&text);
+        return;
+    }
+
+    if text.trim().is_empty() {
+        if !text.is_empty() && !out.is_empty() && !out.ends_with(char::is_whitespace) {
+            out.push(' ');
+        }
+        return;
+    }
+
+    let starts_with_space = text.starts_with(char::is_whitespace);
+    let ends_with_space = text.ends_with(char::is_whitespace);
+
+    if starts_with_space && !out.is_empty() && !out.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+    out.push_str(&text.split_whitespace().collect::<Vec<_>>().join(" "));
+    if ends_with_space {
+        out.push(' ');
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, AocClientError, Verdict};
+    use crate::{day, template::PuzzleId, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    const PUZZLE_HTML: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is missing &amp; the list is:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul><li>Pair up the <code>smallest</code> numbers.</li></ul>
+<p>See <a href="/2024/about">about</a>.</p>
+</article>
+<p>Your puzzle answer was <code>1189304</code>.</p>
+<form method="post"><p>Answer: <input type="text" name="answer"/></p></form>
+</main></body></html>"#;
+
+    /// Serves a single request with a canned response and returns the request head and body.
+    fn mock_server(status: u16, body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                request.push_str(&line);
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (url, server) = mock_server(200, "1 2\n3 4\n");
+        let client = AocClient::new(&url, "abc");
+        assert_eq!(
+            client.input(PuzzleId::new(year!(2024), day!(1))).unwrap(),
+            "1 2\n3 4\n"
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.contains("Cookie: session=abc"));
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let (url, _server) = mock_server(200, PUZZLE_HTML);
+        let client = AocClient::new(&url, "abc");
+        let expected = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "The *Chief Historian* is missing & the list is:",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "- Pair up the `smallest` numbers.",
+            "",
+            "See [about](/2024/about).",
+            "",
+            "Your puzzle answer was `1189304`.",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(
+            client.puzzle(PuzzleId::new(year!(2024), day!(1))).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, server) = mock_server(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "abc");
+        let submission = client
+            .submit(PuzzleId::new(year!(2024), day!(3)), 2, "42")
+            .unwrap();
+        assert_eq!(submission.verdict, Verdict::TooHigh);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/3/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                Verdict::Correct,
+            ),
+            (
+                "That's not the right answer. If you're stuck...",
+                Verdict::Incorrect,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::TooLow,
+            ),
+            (
+                "You gave an answer too recently; you have 30s left to wait.",
+                Verdict::RateLimited,
+            ),
+            (
+                "You don't seem to be solving the right level.",
+                Verdict::AlreadySolved,
+            ),
+        ];
+
+        for (message, verdict) in cases {
+            let html = format!("<article><p>{message}</p></article>");
+            assert_eq!(super::parse_submission(&html).verdict, verdict);
+        }
+
+        for html in [
+            "",
+            "<main><p>Please log in.</p></main>",
+            "<article></article>",
+        ] {
+            assert_eq!(super::parse_submission(html).verdict, Verdict::Unknown);
+        }
+    }
+
+    #[test]
+    fn maps_error_responses() {
+        let (url, _server) = mock_server(400, "Puzzle inputs differ by user.");
+        let client = AocClient::new(&url, "abc");
+        assert!(matches!(
+            client.input(PuzzleId::new(year!(2024), day!(1))),
+            Err(AocClientError::BadSession)
+        ));
+
+        let (url, _server) = mock_server(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        let client = AocClient::new(&url, "abc");
+        assert!(matches!(
+            client.input(PuzzleId::new(year!(2024), day!(1))),
+            Err(AocClientError::NotUnlocked)
+        ));
+
+        let (url, _server) = mock_server(429, "");
+        let client = AocClient::new(&url, "abc");
+        assert!(matches!(
+            client.input(PuzzleId::new(year!(2024), day!(1))),
+            Err(AocClientError::RateLimited)
+        ));
+    }
+
+    #[test]
+    fn reports_network_failures() {
+        let url = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        let client = AocClient::new(&url, "abc");
+        assert!(matches!(
+            client.input(PuzzleId::new(year!(2024), day!(1))),
+            Err(AocClientError::Network(_))
+        ));
+    }
+}
@@ -1,5 +1,25 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::collections::HashSet;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days,
+    run_multi::run_multi,
+    runner::{RunOptions, Solution},
+    Day, DayFilters, Limits, Year,
+};
+
+pub fn handle(
+    year: Year,
+    days: Option<HashSet<Day>>,
+    filters: DayFilters,
+    is_release: bool,
+    limits: Limits,
+    solutions: Option<&[Solution]>,
+) {
+    let days_to_run = filters.apply(year, days.unwrap_or_else(|| all_days(year).collect()));
+    let options = RunOptions {
+        is_release,
+        limits,
+        ..Default::default()
+    };
+    run_multi(year, &days_to_run, options, solutions);
 }
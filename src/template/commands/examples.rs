@@ -0,0 +1,122 @@
+use std::{fs, path::Path, process};
+
+use crate::template::puzzle_examples::PuzzleExamples;
+use crate::template::PuzzleId;
+
+/// An entry of a freshly scaffolded example manifest, see `scaffold.rs`.
+const SCAFFOLDED_EXAMPLE: &str = r#"{ "part": %PART%, "answer": null }"#;
+
+/// Saves the examples of a puzzle description. Existing example files are kept, as they may have been edited,
+/// unless `force` is set.
+pub fn handle(puzzle: PuzzleId, force: bool) {
+    let markdown = match fs::read_to_string(puzzle.puzzle_path()) {
+        Ok(markdown) => markdown,
+        Err(e) => {
+            eprintln!(
+                "Failed to read puzzle description \"{}\": {e}. Run `cargo download {}` first.",
+                puzzle.puzzle_path(),
+                puzzle.day
+            );
+            process::exit(1);
+        }
+    };
+
+    let examples = PuzzleExamples::parse(&markdown);
+
+    if examples.blocks.is_empty() {
+        println!("No examples found in \"{}\".", puzzle.puzzle_path());
+        return;
+    }
+
+    for (index, block) in examples.blocks.iter().enumerate() {
+        let path = format!(
+            "data/{}/examples/{}-{}.txt",
+            puzzle.year,
+            puzzle.day,
+            index + 1
+        );
+        if force || !Path::new(&path).exists() {
+            write_example(&path, block);
+        } else {
+            println!("Kept existing example file \"{path}\"");
+        }
+    }
+
+    // the default example is created by `scaffold`, so it is only filled in while it is still empty.
+    if let Some(part_one) = &examples.parts[0] {
+        let example_path = puzzle.example_path();
+        if force
+            || fs::read_to_string(&example_path)
+                .unwrap_or_default()
+                .is_empty()
+        {
+            write_example(&example_path, &examples.blocks[part_one.block]);
+        }
+    }
+
+    prefill_manifest(puzzle, &examples);
+}
+
+fn write_example(path: &str, contents: &str) {
+    match fs::write(path, contents) {
+        Ok(()) => println!("Created example file \"{path}\""),
+        Err(e) => {
+            eprintln!("Failed to write example file \"{path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Fills the expected answers into the example manifest, as long as its entries are unchanged since scaffolding.
+fn prefill_manifest(puzzle: PuzzleId, examples: &PuzzleExamples) {
+    let manifest_path = puzzle.manifest_path();
+    let Ok(mut manifest) = fs::read_to_string(&manifest_path) else {
+        return;
+    };
+
+    let part_one_block = examples.parts[0].as_ref().map(|p| p.block);
+    let mut is_filled = false;
+
+    for (part, example) in (1..=2).zip(&examples.parts) {
+        let Some(example) = example else {
+            continue;
+        };
+        let Some(answer) = &example.answer else {
+            continue;
+        };
+
+        let template = SCAFFOLDED_EXAMPLE.replace("%PART%", &part.to_string());
+        if !manifest.contains(&template) {
+            continue;
+        }
+
+        // numbers are written as such, as long as JSON can represent them exactly.
+        let answer = match answer.parse::<u64>() {
+            Ok(n) if n <= 1 << 53 => n.to_string(),
+            _ => format!("{answer:?}"),
+        };
+        let file = if Some(example.block) == part_one_block {
+            String::new()
+        } else {
+            format!(r#""file": "{}-{}.txt", "#, puzzle.day, example.block + 1)
+        };
+
+        manifest = manifest.replace(
+            &template,
+            &format!(r#"{{ "part": {part}, {file}"answer": {answer} }}"#),
+        );
+        is_filled = true;
+    }
+
+    if !is_filled {
+        return;
+    }
+
+    match fs::write(&manifest_path, manifest) {
+        Ok(()) => println!("Filled in expected answers in \"{manifest_path}\""),
+        Err(e) => {
+            eprintln!("Failed to update example manifest: {e}");
+            process::exit(1);
+        }
+    }
+}
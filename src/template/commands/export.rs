@@ -0,0 +1,36 @@
+use std::{fs, process};
+
+use crate::template::history::History;
+use crate::template::report::{history_csv, html_report, markdown_report, svg_chart, timings_csv};
+use crate::template::timings::Timings;
+use crate::template::ExportFormat;
+
+/// Exports the stored benchmarks to a file, or to stdout if no output is given.
+/// With `history`, the CSV export contains every run of the benchmark history instead of the stored timings.
+pub fn handle(format: ExportFormat, output: Option<String>, history: bool) {
+    let timings = Timings::read_from_file().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let contents = match format {
+        ExportFormat::Csv if history => history_csv(&History::read_from_file()),
+        ExportFormat::Csv => timings_csv(&timings),
+        ExportFormat::Markdown => markdown_report(&timings, &History::read_from_file()),
+        ExportFormat::Html => html_report(&timings, &History::read_from_file()),
+        ExportFormat::Svg => svg_chart(&timings),
+    };
+
+    let Some(path) = output else {
+        print!("{contents}");
+        return;
+    };
+
+    match fs::write(&path, contents) {
+        Ok(()) => println!("Exported benchmarks to \"{path}\"."),
+        Err(e) => {
+            eprintln!("Failed to write \"{path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
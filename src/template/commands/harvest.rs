@@ -0,0 +1,69 @@
+use std::{collections::HashSet, fs, process};
+
+use crate::template::answers::{accepted_answers, fingerprint, Answers};
+use crate::template::aoc_client::Verdict;
+use crate::template::{all_days, Day, PuzzleId, Year};
+
+/// Records the answers of solved parts, as shown on the downloaded puzzle pages, as correct in `data/answers.json`.
+/// Answers are stored for the input of the day that is on disk, as that is the input they were earned with.
+pub fn handle(year: Year, days: Option<HashSet<Day>>) {
+    let mut ledger = Answers::read_from_file().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+    let mut harvested = 0;
+
+    for day in all_days(year) {
+        if days.as_ref().is_some_and(|days| !days.contains(&day)) {
+            continue;
+        }
+
+        let puzzle = PuzzleId::new(year, day);
+        let Ok(markdown) = fs::read_to_string(puzzle.puzzle_path()) else {
+            continue;
+        };
+
+        let answers = accepted_answers(&markdown);
+        if answers.is_empty() {
+            continue;
+        }
+
+        let Ok(input) = fs::read_to_string(puzzle.input_path()) else {
+            eprintln!(
+                "Skipping day {day}: no input in \"{}\".",
+                puzzle.input_path()
+            );
+            continue;
+        };
+        let input_id = fingerprint(&input);
+
+        for (part, answer) in (1..=2).zip(&answers) {
+            match ledger.correct_answer(puzzle, part, &input_id) {
+                Some(known) if known == answer => {}
+                Some(known) => {
+                    eprintln!(
+                        "Skipping day {day} part {part}: the puzzle page shows `{answer}`, \
+                        but `{known}` is recorded as correct."
+                    );
+                }
+                None => {
+                    ledger.record(puzzle, part, &input_id, answer, Verdict::Correct);
+                    println!("Day {day} Part {part}: {answer}");
+                    harvested += 1;
+                }
+            }
+        }
+    }
+
+    if harvested == 0 {
+        println!("No new answers found.");
+        return;
+    }
+
+    if let Err(e) = ledger.store_file() {
+        eprintln!("Failed to store answers: {e}");
+        process::exit(1);
+    }
+
+    println!("🎄 Stored {harvested} new answer(s) in \"data/answers.json\".");
+}
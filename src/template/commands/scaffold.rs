@@ -1,13 +1,20 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
     process,
 };
 
-use crate::template::Day;
+use crate::template::scaffold_template::{puzzle_title, ScaffoldTemplate};
+use crate::template::PuzzleId;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// An example manifest that expects both parts to be unsolved. See `build.rs` for its format.
+const MANIFEST_TEMPLATE: &str = r#"{
+  "examples": [
+    { "part": 1, "answer": null },
+    { "part": 2, "answer": null }
+  ]
+}
+"#;
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,18 +26,18 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
-}
+pub fn handle(puzzle: PuzzleId, template: &ScaffoldTemplate, overwrite: bool) {
+    let input_path = puzzle.input_path();
+    let example_path = puzzle.example_path();
+    let manifest_path = puzzle.manifest_path();
+    let module_path = puzzle.bin_path();
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    for folder in ["inputs", "examples", "puzzles"] {
+        if let Err(e) = fs::create_dir_all(format!("data/{}/{folder}", puzzle.year)) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +47,17 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    // the title is known if the puzzle was downloaded before scaffolding.
+    let title = fs::read_to_string(puzzle.puzzle_path())
+        .ok()
+        .and_then(|markdown| puzzle_title(&markdown));
+
+    match file.write_all(template.render(puzzle, title.as_deref()).as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!(
+                "Created module file \"{}\" from template `{}`",
+                &module_path, template.name
+            );
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -54,26 +65,51 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
+    // inputs and examples are kept, e.g. if they were downloaded before scaffolding.
+    match safe_create_file(&input_path, false) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing input file \"{}\"", &input_path);
+        }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
+    match safe_create_file(&example_path, false) {
         Ok(_) => {
             println!("Created empty example file \"{}\"", &example_path);
         }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing example file \"{}\"", &example_path);
+        }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
         }
     }
 
+    match safe_create_file(&manifest_path, overwrite)
+        .and_then(|mut file| file.write_all(MANIFEST_TEMPLATE.as_bytes()))
+    {
+        Ok(()) => {
+            println!("Created example manifest \"{}\"", &manifest_path);
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing example manifest \"{}\"", &manifest_path);
+        }
+        Err(e) => {
+            eprintln!("Failed to create example manifest: {e}");
+            process::exit(1);
+        }
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
 }
@@ -1,9 +1,17 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{InputSource, Limits, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    part: Option<u8>,
+    limits: Limits,
+    source: &InputSource,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -12,8 +20,14 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if release {
+            cmd_args.push("--release".to_string());
+        }
+        // the memory limit is enforced by the counting allocator.
+        if limits.memory.is_some() {
+            cmd_args.extend(["--features".to_string(), "count-alloc".to_string()]);
+        }
     }
 
     cmd_args.push("--".to_string());
@@ -23,6 +37,14 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    cmd_args.extend(limits.to_args());
+    cmd_args.extend(source.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -1,29 +1,83 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
+use crate::template::environment::Environment;
+use crate::template::history::{Delta, History, Run};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, readme_benchmarks,
+    runner::{format_nanos, part_label, RunOptions, Solution, PARSE_PART},
+    Comparison, Day, DayFilters, PuzzleId, Year, ANSI_BOLD, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Benchmarks a set of days. The options select a single part, heap profiling and limits.
+/// Every run is appended to the benchmark history. With a comparison, the run is compared to an earlier one,
+/// and the process exits with an error if a part regressed.
+/// Timings of another machine than the stored timings are only stored with `force`.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    days: Option<HashSet<Day>>,
+    filters: DayFilters,
+    run_all: bool,
+    store: bool,
+    force: bool,
+    options: RunOptions,
+    comparison: Option<Comparison>,
+    solutions: Option<&[Solution]>,
+) {
+    // a file that cannot be read, e.g. of a newer version of the template, would be overwritten when storing.
+    let stored_timings = Timings::read_from_file().unwrap_or_else(|e| {
+        if store {
+            eprintln!("Refusing to store benchmarks: {e}");
+            process::exit(1);
+        }
+        eprintln!("{e}");
+        Timings::default()
+    });
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+    // checked before benchmarking, so a long run is not lost. The toolchain does not matter for this check.
+    if store && !force {
+        if let Err(e) = stored_timings.check_host(&Environment::detect("release")) {
+            eprintln!("Refusing to store benchmarks: {e}.");
+            eprintln!(
+                "Pass `--force` to merge the timings anyway, or remove \"data/timings.json\" to start over."
+            );
+            process::exit(1);
+        }
+    }
+    // read before the new run is appended, so `--compare last` refers to the previous run.
+    let history = comparison.as_ref().map(|_| History::read_from_file());
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let days_to_run = days.unwrap_or_else(|| {
+        if run_all || !filters.is_empty() {
+            all_days(year).collect()
+        } else {
+            // when neither `--all` nor a filter is set, filter out days that are fully benched.
+            all_days(year)
+                .filter(|day| !stored_timings.is_day_complete(PuzzleId::new(year, *day)))
+                .collect()
+        }
+    });
+    let days_to_run = filters.apply(year, days_to_run);
+
+    let options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        ..options
+    };
+    let timings = run_multi(year, &days_to_run, options, solutions)
+        .timings
+        .unwrap();
+
+    if !timings.data.is_empty() {
+        if let Err(e) = History::append(&Run::new(timings.clone())) {
+            eprintln!("Failed to append run to the benchmark history: {e}");
+        }
+    }
 
     if store {
+        // parts that did not run keep their stored timings, e.g. part 1 when timing `--part 2`.
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
@@ -37,4 +91,61 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if let (Some(comparison), Some(history)) = (comparison, history) {
+        let regressions = print_comparison(&timings, &history, &comparison);
+        if regressions > 0 {
+            eprintln!(
+                "\n{ANSI_BOLD}✖ {regressions} part(s) got slower by more than {}%.{ANSI_RESET}",
+                comparison.threshold
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Prints the change of every part that was timed, and returns the number of regressions.
+fn print_comparison(timings: &Timings, history: &History, comparison: &Comparison) -> usize {
+    println!(
+        "\nCompared to {} (threshold {}%):",
+        comparison.baseline, comparison.threshold
+    );
+
+    let mut regressions = 0;
+    for timing in &timings.data {
+        for part in [PARSE_PART, 1, 2] {
+            let Some(after) = timing.part(part) else {
+                continue;
+            };
+            let label = format!("Day {} {}", timing.day, part_label(part));
+
+            let Some((run, before)) = history.baseline(timing.puzzle(), part, &comparison.baseline)
+            else {
+                println!("{label}: {} (no earlier run)", format_nanos(after.nanos));
+                continue;
+            };
+
+            let delta = Delta::new(before, after);
+            let verdict = if delta.is_regression(comparison.threshold) {
+                regressions += 1;
+                format!("{ANSI_BOLD}regression{ANSI_RESET}")
+            } else if !delta.is_significant() {
+                "within noise".into()
+            } else if delta.percent() > 0.0 {
+                "slower".into()
+            } else {
+                "faster".into()
+            };
+
+            println!(
+                "{label}: {} -> {} ({:+.1}%, vs {}) {verdict}",
+                format_nanos(delta.before),
+                format_nanos(delta.after),
+                delta.percent(),
+                run.label()
+            );
+        }
+    }
+
+    regressions
 }
@@ -0,0 +1,245 @@
+use std::{collections::HashSet, fs, process};
+
+use crate::template::answers::{fingerprint, Answers};
+use crate::template::input_set::{constant_parts, read_inputs};
+use crate::template::record::PartStatus;
+use crate::template::run_multi::{
+    aborted_parts, child_commands::parse_answers, run_day, run_multi, AbortedPart, PartAnswers,
+};
+use crate::template::runner::{RunOptions, Solution, PARSE_PART};
+use crate::template::{all_days, Day, DayFilters, Limits, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail,
+    /// The part panicked or exceeded a limit.
+    Aborted(PartStatus),
+    Missing,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Aborted(status) => status.label(),
+            Status::Missing => "-",
+        }
+    }
+}
+
+pub fn handle(
+    year: Year,
+    days: Option<HashSet<Day>>,
+    filters: DayFilters,
+    is_release: bool,
+    limits: Limits,
+    all_inputs: bool,
+    solutions: Option<&[Solution]>,
+) {
+    let ledger = Answers::read_from_file().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+    let days_to_run = filters.apply(year, days.unwrap_or_else(|| all_days(year).collect()));
+    let options = RunOptions {
+        is_release,
+        limits,
+        ..Default::default()
+    };
+
+    if all_inputs {
+        verify_all_inputs(year, &days_to_run, options, solutions, &ledger);
+        return;
+    }
+
+    let run = run_multi(year, &days_to_run, options, solutions);
+
+    let mut rows: Vec<(PuzzleId, [Status; 2])> = vec![];
+    let mut mismatches: Vec<String> = vec![];
+
+    for (puzzle, answers) in run.answers {
+        let input = fs::read_to_string(puzzle.input_path()).unwrap_or_default();
+        let input_id = fingerprint(&input);
+        let mut statuses = [Status::Missing; 2];
+
+        for (index, actual) in answers.iter().enumerate() {
+            let part = index as u8 + 1;
+            let expected = ledger.correct_answer(puzzle, part, &input_id);
+            let (status, mismatch) = check_part(puzzle, part, actual, expected, &run.aborted);
+
+            statuses[index] = status;
+            if let Some(mismatch) = mismatch {
+                mismatches.push(format!("Day {} Part {part}: {mismatch}", puzzle.day));
+            }
+        }
+
+        rows.push((puzzle, statuses));
+    }
+
+    println!("\n{ANSI_BOLD}Verification ({year}){ANSI_RESET}");
+    println!("| Day | Part 1 | Part 2 |");
+    for (puzzle, [part_1, part_2]) in &rows {
+        println!(
+            "| {}  | {:<6} | {:<6} |",
+            puzzle.day,
+            part_1.as_str(),
+            part_2.as_str()
+        );
+    }
+
+    print_summary(rows.iter().flat_map(|(_, statuses)| statuses));
+    exit_on_mismatches(&mismatches);
+}
+
+/// Runs every day against the puzzle input and all inputs in `data/{year}/inputs/{day}/`, and flags parts
+/// that return the same answer for different inputs.
+fn verify_all_inputs(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    options: RunOptions,
+    solutions: Option<&[Solution]>,
+    ledger: &Answers,
+) {
+    let mut rows: Vec<(PuzzleId, String, [Status; 2])> = vec![];
+    let mut mismatches: Vec<String> = vec![];
+    let mut constant: Vec<String> = vec![];
+    let mut single_input_days = 0;
+
+    for day in all_days(year).filter(|day| days_to_run.contains(day)) {
+        let puzzle = PuzzleId::new(year, day);
+        let inputs = read_inputs(puzzle, ledger).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+
+        let mut answers: Vec<PartAnswers> = vec![];
+
+        for input in &inputs {
+            println!("\n{ANSI_BOLD}Day {day} ({}){ANSI_RESET}", input.name());
+            println!("------");
+
+            let records = run_day(puzzle, &input.source(), options, solutions);
+            if records.is_empty() {
+                println!("Not solved.");
+                break;
+            }
+
+            let aborted = aborted_parts(puzzle, &records);
+            let actual = parse_answers(&records);
+            let mut statuses = [Status::Missing; 2];
+
+            for (index, expected) in input.answers.iter().enumerate() {
+                let part = index as u8 + 1;
+                let (status, mismatch) =
+                    check_part(puzzle, part, &actual[index], expected.as_deref(), &aborted);
+
+                statuses[index] = status;
+                if let Some(mismatch) = mismatch {
+                    mismatches.push(format!(
+                        "Day {day} Part {part} ({}): {mismatch}",
+                        input.name()
+                    ));
+                }
+            }
+
+            rows.push((puzzle, input.name().to_string(), statuses));
+            answers.push(actual);
+        }
+
+        if answers.len() < 2 {
+            single_input_days += usize::from(!answers.is_empty());
+            continue;
+        }
+
+        for part in constant_parts(&inputs, &answers) {
+            constant.push(format!(
+                "Day {day} Part {}: returns `{}` for all {} inputs, it probably ignores its input.",
+                part.part, part.answer, part.inputs
+            ));
+        }
+    }
+
+    let width = rows
+        .iter()
+        .map(|(_, name, _)| name.len())
+        .max()
+        .unwrap_or_default()
+        .max("Input".len());
+
+    println!("\n{ANSI_BOLD}Verification of all inputs ({year}){ANSI_RESET}");
+    println!("| Day | {:<width$} | Part 1 | Part 2 |", "Input");
+    for (puzzle, name, [part_1, part_2]) in &rows {
+        println!(
+            "| {}  | {name:<width$} | {:<6} | {:<6} |",
+            puzzle.day,
+            part_1.as_str(),
+            part_2.as_str()
+        );
+    }
+
+    print_summary(rows.iter().flat_map(|(_, _, statuses)| statuses));
+
+    if single_input_days > 0 {
+        println!(
+            "{single_input_days} of the days only have one input, add more to `data/{year}/inputs/<day>/` to check that their solutions use it."
+        );
+    }
+
+    mismatches.extend(constant);
+    exit_on_mismatches(&mismatches);
+}
+
+/// Compares the answer of a part to its expected answer. Returns the status of the part, and why it did not pass.
+fn check_part(
+    puzzle: PuzzleId,
+    part: u8,
+    actual: &Option<String>,
+    expected: Option<&str>,
+    aborted: &[AbortedPart],
+) -> (Status, Option<String>) {
+    // an aborted parse phase aborts both parts.
+    if let Some(abort) = aborted
+        .iter()
+        .find(|a| a.puzzle == puzzle && (a.part == part || a.part == PARSE_PART))
+    {
+        return (Status::Aborted(abort.status), Some(abort.error.clone()));
+    }
+
+    let Some(expected) = expected else {
+        return (Status::Missing, None);
+    };
+
+    if actual.as_deref() == Some(expected) {
+        (Status::Pass, None)
+    } else {
+        let actual = actual.as_deref().unwrap_or("✖");
+        (
+            Status::Fail,
+            Some(format!("expected `{expected}`, got `{actual}`")),
+        )
+    }
+}
+
+fn print_summary<'a>(statuses: impl Iterator<Item = &'a Status> + Clone) {
+    let count = |is_match: fn(&Status) -> bool| statuses.clone().filter(|s| is_match(s)).count();
+
+    println!(
+        "\n{} passed, {} failed, {} aborted, {} missing.",
+        count(|s| *s == Status::Pass),
+        count(|s| *s == Status::Fail),
+        count(|s| matches!(s, Status::Aborted(_))),
+        count(|s| *s == Status::Missing)
+    );
+}
+
+fn exit_on_mismatches(mismatches: &[String]) {
+    if !mismatches.is_empty() {
+        println!();
+        for mismatch in mismatches {
+            eprintln!("{mismatch}");
+        }
+        process::exit(1);
+    }
+}
@@ -1,20 +1,27 @@
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::Year;
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+pub(super) const SERVER_UTC_OFFSET: i32 = -5;
+
+/// The last day of the longest advent calendar.
+pub const MAX_DAY: u8 = 25;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Some years have shorter calendars, see [`Year::last_day`].
 ///
 /// # Display
 /// This value displays as a two digit number.
 ///
 /// ```
-/// # use advent_of_code::Day;
+/// # use advent_of_code::template::Day;
 /// let day = Day::new(8).unwrap();
 /// assert_eq!(day.to_string(), "08")
 /// ```
@@ -25,12 +32,30 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > MAX_DAY {
             return None;
         }
         Some(Self(day))
     }
 
+    /// Creates a [`Day`] from the provided value if it's part of the calendar of `year`,
+    /// returns [`None`] otherwise.
+    pub fn new_in_year(day: u8, year: Year) -> Option<Self> {
+        if day == 0 || day > year.last_day() {
+            return None;
+        }
+        Some(Self(day))
+    }
+
+    /// Parses a day number of the calendar of `year`.
+    pub fn from_str_in_year(s: &str, year: Year) -> Result<Self, DayFromStrError> {
+        let error = DayFromStrError {
+            last_day: year.last_day(),
+        };
+        let day = s.parse().map_err(|_| error)?;
+        Self::new_in_year(day, year).ok_or(error)
+    }
+
     // Not part of the public API
     #[doc(hidden)]
     pub const fn __new_unchecked(day: u8) -> Self {
@@ -45,15 +70,15 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's part of this year's advent calendar, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
+        if today.month() != 12 {
+            return None;
         }
+        let year = Year::new(u16::try_from(today.year()).ok()?)?;
+        Self::new_in_year(u8::try_from(today.day()).ok()?, year)
     }
 }
 
@@ -81,39 +106,45 @@ impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        let error = DayFromStrError { last_day: MAX_DAY };
+        let day = s.parse().map_err(|_| error)?;
+        Self::new(day).ok_or(error)
     }
 }
 
 /// An error which can be returned when parsing a [`Day`].
-#[derive(Debug)]
-pub struct DayFromStrError;
+#[derive(Debug, Clone, Copy)]
+pub struct DayFromStrError {
+    last_day: u8,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", self.last_day)
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of the advent calendar of `year`, starting with the 1st.
+pub fn all_days(year: Year) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the advent calendar of a year, starting with the 1st.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.last_day(),
+        }
     }
 }
 
@@ -121,10 +152,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -134,7 +165,64 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// Parses a comma-separated set of days of the advent calendar of `year`, e.g. `1-5,7,20-`.
+/// Ranges include both ends, and an open end extends them to the first or last day.
+pub fn parse_day_set(s: &str, year: Year) -> Result<HashSet<Day>, DaySetFromStrError> {
+    let mut days = HashSet::new();
+
+    for item in s.split(',').map(str::trim) {
+        let error = |reason: String| DaySetFromStrError {
+            item: item.into(),
+            reason,
+        };
+        let parse_day =
+            |s: &str| Day::from_str_in_year(s.trim(), year).map_err(|e| error(e.to_string()));
+
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let start = if start.trim().is_empty() {
+                    Day(1)
+                } else {
+                    parse_day(start)?
+                };
+                let end = if end.trim().is_empty() {
+                    Day(year.last_day())
+                } else {
+                    parse_day(end)?
+                };
+                if start > end {
+                    return Err(error("the range ends before it starts".into()));
+                }
+                days.extend((start.0..=end.0).map(Day));
+            }
+            None => {
+                days.insert(parse_day(item)?);
+            }
+        }
+    }
+
+    Ok(days)
+}
+
+/// An error which can be returned when parsing a set of days.
+#[derive(Debug, Clone)]
+pub struct DaySetFromStrError {
+    item: String,
+    reason: String,
+}
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid day set entry `{}`: {}", self.item, self.reason)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
+/// If a year is passed as second parameter, the day is checked against the calendar of that year.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
@@ -148,17 +236,30 @@ macro_rules! day {
         );
         $crate::template::Day::__new_unchecked($day)
     }};
+    ($day:expr, $year:expr) => {{
+        const _ASSERT: () = assert!(
+            $day != 0 && $day <= $crate::template::Year::__last_day($year),
+            concat!(
+                "invalid day number `",
+                $day,
+                "`, expecting a day of the advent calendar of ",
+                $year
+            ),
+        );
+        $crate::template::Day::__new_unchecked($day)
+    }};
 }
 
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, parse_day_set, Day};
+    use crate::year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(year!(2024));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +288,47 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_for_short_calendars() {
+        let days: Vec<Day> = all_days(year!(2025)).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
+
+    #[test]
+    fn parses_day_sets() {
+        let days = parse_day_set("1-3, 7,20-", year!(2024)).unwrap();
+        let mut days: Vec<u8> = days.into_iter().map(Day::into_inner).collect();
+        days.sort_unstable();
+        assert_eq!(days, [1, 2, 3, 7, 20, 21, 22, 23, 24, 25]);
+
+        assert_eq!(parse_day_set("-2", year!(2025)).unwrap().len(), 2);
+        assert_eq!(parse_day_set("10-", year!(2025)).unwrap().len(), 3);
+
+        let error = parse_day_set("5-3", year!(2024)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid day set entry `5-3`: the range ends before it starts"
+        );
+        let error = parse_day_set("1,13", year!(2025)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid day set entry `13`: expecting a day number between 1 and 12"
+        );
+        assert!(parse_day_set("", year!(2024)).is_err());
+    }
+
+    #[test]
+    fn parses_days_of_a_year() {
+        assert_eq!(Day::from_str_in_year("12", year!(2025)).unwrap(), Day(12));
+        assert_eq!(Day::from_str_in_year("25", year!(2024)).unwrap(), Day(25));
+
+        let error = Day::from_str_in_year("13", year!(2025)).unwrap_err();
+        assert_eq!(error.to_string(), "expecting a day number between 1 and 12");
+        assert!(Day::from_str_in_year("0", year!(2024)).is_err());
+        assert!(Day::from_str_in_year("26", year!(2024)).is_err());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,131 @@
+use std::{collections::HashSet, fs, time::Duration};
+
+use crate::template::answers::{fingerprint, Answers};
+use crate::template::record::PartStatus;
+use crate::template::timings::Timings;
+use crate::template::{Day, PuzzleId, Year};
+
+/// Days whose stored benchmark takes at least this long count as slow.
+pub const SLOW_THRESHOLD: Duration = Duration::from_millis(100);
+
+/// Status filters that narrow down the days run by `all`, `time` and `verify`.
+/// All filters that are set have to match.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DayFilters {
+    /// Only days without a known correct answer for both parts of the current input.
+    pub unsolved: bool,
+    /// Only days that took longer than [`SLOW_THRESHOLD`] or timed out in the last stored benchmark.
+    pub slow: bool,
+}
+
+impl DayFilters {
+    pub fn is_empty(self) -> bool {
+        !self.unsolved && !self.slow
+    }
+
+    /// Keeps the days of `year` in `days` that match all filters.
+    pub fn apply(self, year: Year, days: HashSet<Day>) -> HashSet<Day> {
+        if self.is_empty() {
+            return days;
+        }
+
+        let answers = Answers::read_from_file().unwrap_or_else(|e| {
+            eprintln!("{e}");
+            Answers::default()
+        });
+        let timings = Timings::read_from_file().unwrap_or_else(|e| {
+            eprintln!("{e}");
+            Timings::default()
+        });
+
+        days.into_iter()
+            .filter(|day| {
+                let puzzle = PuzzleId::new(year, *day);
+                (!self.unsolved || is_unsolved(&answers, puzzle, &read_input(puzzle)))
+                    && (!self.slow || is_slow(&timings, puzzle))
+            })
+            .collect()
+    }
+}
+
+/// The input on disk. Days without an input are fingerprinted as empty.
+fn read_input(puzzle: PuzzleId) -> String {
+    fs::read_to_string(puzzle.input_path()).unwrap_or_default()
+}
+
+/// A day is unsolved until both parts have a correct answer for its input.
+fn is_unsolved(answers: &Answers, puzzle: PuzzleId, input: &str) -> bool {
+    let input_id = fingerprint(input);
+    (1..=2).any(|part| answers.correct_answer(puzzle, part, &input_id).is_none())
+}
+
+fn is_slow(timings: &Timings, puzzle: PuzzleId) -> bool {
+    timings.data.iter().any(|t| {
+        t.puzzle() == puzzle
+            && (t.total_nanos() >= SLOW_THRESHOLD.as_nanos() as f64
+                || t.part_1_status == Some(PartStatus::Timeout)
+                || t.part_2_status == Some(PartStatus::Timeout))
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_slow, is_unsolved};
+    use crate::template::answers::{fingerprint, Answers};
+    use crate::template::aoc_client::Verdict;
+    use crate::template::record::PartStatus;
+    use crate::template::timings::{PartTiming, Timing, Timings};
+    use crate::{day, template::PuzzleId, year};
+
+    fn timing(day: u8, total_nanos: f64, part_2_status: Option<PartStatus>) -> Timing {
+        Timing {
+            year: year!(2024),
+            day: crate::template::Day::new(day).unwrap(),
+            parse: None,
+            part_1: Some(PartTiming {
+                nanos: total_nanos,
+                samples: 10,
+                ..Default::default()
+            }),
+            part_2: None,
+            part_1_status: None,
+            part_2_status,
+        }
+    }
+
+    #[test]
+    fn filters_slow_days() {
+        let timings = Timings {
+            data: vec![
+                timing(1, 1_000_000.0, None),
+                timing(2, 250_000_000.0, None),
+                timing(3, 0.0, Some(PartStatus::Timeout)),
+            ],
+            environment: None,
+        };
+
+        assert!(!is_slow(&timings, PuzzleId::new(year!(2024), day!(1))));
+        assert!(is_slow(&timings, PuzzleId::new(year!(2024), day!(2))));
+        assert!(is_slow(&timings, PuzzleId::new(year!(2024), day!(3))));
+        assert!(!is_slow(&timings, PuzzleId::new(year!(2024), day!(4))));
+    }
+
+    #[test]
+    fn filters_unsolved_days() {
+        let puzzle = PuzzleId::new(year!(2024), day!(25));
+        let input = "#####\n.####\n";
+        let mut answers = Answers::default();
+        assert!(is_unsolved(&answers, puzzle, input));
+
+        answers.record(puzzle, 1, &fingerprint(input), "42", Verdict::Correct);
+        assert!(is_unsolved(&answers, puzzle, input));
+
+        answers.record(puzzle, 2, &fingerprint(input), "7", Verdict::Correct);
+        assert!(!is_unsolved(&answers, puzzle, input));
+
+        // answers are only valid for the input they were computed for.
+        assert!(is_unsolved(&answers, puzzle, "#####\n"));
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, env, fmt::Display, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+/// Version of the compiler that built the template, e.g. `1.83.0 (90b35a623 2024-11-26)`. Set by `build.rs`.
+const RUSTC_VERSION: &str = env!("AOC_RUSTC_VERSION");
+
+/// The machine and toolchain that benchmarks were measured with.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Environment {
+    pub host: String,
+    /// Model name of the processor, e.g. `AMD Ryzen 7 5800X 8-Core Processor`.
+    pub cpu: String,
+    /// Number of threads the solutions can run on.
+    pub cores: usize,
+    /// Operating system and architecture, e.g. `linux-x86_64`.
+    pub os: String,
+    pub rustc: String,
+    /// Build profile of the solutions, e.g. `release`.
+    pub profile: String,
+}
+
+impl Environment {
+    /// Detects the environment of the current process. Solutions are built with the given profile.
+    pub fn detect(profile: &str) -> Self {
+        Environment {
+            host: host_name(),
+            cpu: cpu_model().unwrap_or_else(|| "unknown".to_string()),
+            cores: thread::available_parallelism().map_or(1, usize::from),
+            os: format!("{}-{}", env::consts::OS, env::consts::ARCH),
+            rustc: RUSTC_VERSION.to_string(),
+            profile: profile.to_string(),
+        }
+    }
+
+    /// Whether two environments are the same machine. The toolchain may differ, e.g. after an update.
+    pub fn is_same_host(&self, other: &Environment) -> bool {
+        self.host == other.host
+            && self.cpu == other.cpu
+            && self.cores == other.cores
+            && self.os == other.os
+    }
+}
+
+impl Display for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cores = if self.cores == 1 { "core" } else { "cores" };
+        write!(
+            f,
+            "`{}` ({}, {} {cores}, {}) with rustc {}, {} profile",
+            self.host, self.cpu, self.cores, self.os, self.rustc, self.profile
+        )
+    }
+}
+
+fn host_name() -> String {
+    env::var("HOSTNAME")
+        .or_else(|_| env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| command(&["hostname"]))
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Reads the processor model from `/proc/cpuinfo` on linux, `sysctl` on macOS and the environment on windows.
+fn cpu_model() -> Option<String> {
+    let model = fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| {
+            cpuinfo.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                matches!(key.trim(), "model name" | "Hardware" | "Model").then(|| value.to_string())
+            })
+        })
+        .or_else(|| command(&["sysctl", "-n", "machdep.cpu.brand_string"]))
+        .or_else(|| env::var("PROCESSOR_IDENTIFIER").ok())?;
+
+    // some models are padded with whitespace, e.g. `Intel(R) Core(TM) i7  CPU`.
+    Some(model.split_whitespace().collect::<Vec<_>>().join(" ")).filter(|m| !m.is_empty())
+}
+
+fn command(args: &[&str]) -> Option<String> {
+    let output = Command::new(args[0]).args(&args[1..]).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Environment> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("host".into(), JsonValue::String(value.host.clone()));
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("os".into(), JsonValue::String(value.os.clone()));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected environment to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected environment.{key} to be a string."))
+        };
+
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .map(|cores| *cores as usize)
+            .ok_or("Expected environment.cores to be a number.")?;
+
+        Ok(Environment {
+            host: string("host")?,
+            cpu: string("cpu")?,
+            cores,
+            os: string("os")?,
+            rustc: string("rustc")?,
+            profile: string("profile")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Environment;
+
+    fn environment() -> Environment {
+        Environment {
+            host: "laptop".into(),
+            cpu: "Apple M2".into(),
+            cores: 8,
+            os: "macos-aarch64".into(),
+            rustc: "1.83.0".into(),
+            profile: "release".into(),
+        }
+    }
+
+    #[test]
+    fn detects_environments() {
+        let environment = Environment::detect("release");
+        assert!(!environment.host.is_empty());
+        assert!(environment.cores > 0);
+        assert!(environment.rustc.starts_with("1."));
+        assert_eq!(environment.profile, "release");
+    }
+
+    #[test]
+    fn compares_hosts() {
+        let updated = Environment {
+            rustc: "1.84.0".into(),
+            profile: "dev".into(),
+            ..environment()
+        };
+        assert!(environment().is_same_host(&updated));
+
+        let other = Environment {
+            host: "desktop".into(),
+            ..environment()
+        };
+        assert!(!environment().is_same_host(&other));
+    }
+
+    #[test]
+    fn roundtrips_environments() {
+        let json = JsonValue::from(&environment());
+        assert_eq!(Environment::try_from(&json), Ok(environment()));
+        assert_eq!(
+            environment().to_string(),
+            "`laptop` (Apple M2, 8 cores, macos-aarch64) with rustc 1.83.0, release profile"
+        );
+    }
+}
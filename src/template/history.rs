@@ -0,0 +1,463 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    timings::{PartTiming, Timings},
+    PuzzleId,
+};
+
+static HISTORY_FILE_PATH: &str = "./data/history.jsonl";
+
+/// Parts that got slower by more than this percentage are reported as regressions by `--compare`.
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// A single run of `cargo time`, as recorded in the benchmark history.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// Hash of the commit that was checked out, if the project is a git repository.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub is_dirty: bool,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Timings of the run, with the machine and toolchain they were measured with.
+    pub timings: Timings,
+}
+
+/// All recorded runs, oldest first.
+/// Stored as one JSON object per line, so runs are appended without rewriting the file.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+/// The run that `cargo time --compare` compares against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Baseline {
+    /// The newest run that timed a part.
+    Last,
+    /// The newest run of a commit without uncommitted changes. Holds the full hash.
+    Commit(String),
+}
+
+/// Options of `cargo time --compare <rev|last>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub baseline: Baseline,
+    /// Percentage above which a slower part is a regression.
+    pub threshold: f64,
+}
+
+/// The change of the mean duration of a part between two runs. Durations are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub before: f64,
+    pub after: f64,
+    /// Combined standard deviation of both runs, if both stored their statistics.
+    pub spread: Option<f64>,
+}
+
+impl Run {
+    /// Tags timings with the current commit and time.
+    pub fn new(timings: Timings) -> Self {
+        Run {
+            commit: git(&["rev-parse", "HEAD"]),
+            is_dirty: git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|status| !status.is_empty()),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            timings,
+        }
+    }
+
+    /// The commit of the run as shown in the output, e.g. `1a2b3c4*` for a working tree with changes.
+    pub fn label(&self) -> String {
+        let commit = self
+            .commit
+            .as_deref()
+            .map_or("unknown", |commit| &commit[..commit.len().min(7)]);
+        let dirty = if self.is_dirty { "*" } else { "" };
+        format!("{commit}{dirty}")
+    }
+}
+
+impl History {
+    /// Appends a run to the history file.
+    pub fn append(run: &Run) -> Result<(), Error> {
+        let line = JsonValue::from(run)
+            .stringify()
+            .map_err(|e| Error::other(e.to_string()))?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(HISTORY_FILE_PATH)?;
+        writeln!(file, "{line}")
+    }
+
+    /// Reads the history file. If not present, returns an empty history.
+    /// Lines that cannot be read are reported and skipped, so a single bad line does not lose the history.
+    pub fn read_from_file() -> Self {
+        let Ok(contents) = fs::read_to_string(HISTORY_FILE_PATH) else {
+            return History::default();
+        };
+
+        let (history, errors) = History::parse(&contents);
+        for e in errors {
+            eprintln!("Skipping run in \"{HISTORY_FILE_PATH}\": {e}");
+        }
+        history
+    }
+
+    fn parse(contents: &str) -> (Self, Vec<String>) {
+        let mut runs = vec![];
+        let mut errors = vec![];
+
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match JsonValue::from_str(line)
+                .map_err(|_| "not valid JSON.".to_string())
+                .and_then(|json| Run::try_from(&json))
+            {
+                Ok(run) => runs.push(run),
+                Err(e) => errors.push(format!("line {}: {e}", index + 1)),
+            }
+        }
+
+        (History { runs }, errors)
+    }
+
+    /// The newest run that timed a part of a puzzle and matches the baseline, with that timing.
+    /// Part `0` is the parse phase.
+    pub fn baseline(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        baseline: &Baseline,
+    ) -> Option<(&Run, &PartTiming)> {
+        self.runs
+            .iter()
+            .rev()
+            .filter(|run| match baseline {
+                Baseline::Last => true,
+                Baseline::Commit(hash) => !run.is_dirty && run.commit.as_ref() == Some(hash),
+            })
+            .find_map(|run| {
+                let timing = run.timings.data.iter().find(|t| t.puzzle() == puzzle)?;
+                Some((run, timing.part(part)?))
+            })
+    }
+}
+
+impl Baseline {
+    /// Parses `last` or a git revision, e.g. `main` or `HEAD~2`, which is resolved to its commit.
+    pub fn parse(s: &str) -> Result<Self, BaselineFromStrError> {
+        if s == "last" {
+            return Ok(Baseline::Last);
+        }
+        git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{s}^{{commit}}"),
+        ])
+        .map(Baseline::Commit)
+        .ok_or_else(|| BaselineFromStrError(s.to_string()))
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Baseline::Last => write!(f, "the last run"),
+            Baseline::Commit(hash) => write!(f, "commit {}", &hash[..hash.len().min(7)]),
+        }
+    }
+}
+
+impl Comparison {
+    pub fn new(baseline: Baseline, threshold: Option<f64>) -> Self {
+        Comparison {
+            baseline,
+            threshold: threshold.unwrap_or(DEFAULT_THRESHOLD),
+        }
+    }
+
+    /// Parse a threshold in percent, e.g. `5`.
+    pub fn parse_threshold(s: &str) -> Result<f64, ThresholdFromStrError> {
+        f64::from_str(s)
+            .ok()
+            .filter(|percent| *percent >= 0.0)
+            .ok_or(ThresholdFromStrError)
+    }
+}
+
+impl Delta {
+    pub fn new(before: &PartTiming, after: &PartTiming) -> Self {
+        let spread = match (&before.stats, &after.stats) {
+            (Some(before), Some(after)) => Some(before.stddev.hypot(after.stddev)),
+            _ => None,
+        };
+        Delta {
+            before: before.nanos,
+            after: after.nanos,
+            spread,
+        }
+    }
+
+    /// Change of the duration in percent. Positive if the part got slower.
+    pub fn percent(&self) -> f64 {
+        if self.before == 0.0 {
+            return 0.0;
+        }
+        (self.after - self.before) / self.before * 100.0
+    }
+
+    /// A change is significant if it exceeds the combined spread of the samples of both runs.
+    /// Without stored statistics, the spread is unknown and no change is significant.
+    pub fn is_significant(&self) -> bool {
+        self.spread
+            .is_some_and(|spread| (self.after - self.before).abs() > spread)
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.is_significant() && self.percent() > threshold
+    }
+}
+
+/// Runs a git command in the project and returns its trimmed output, if it succeeded.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Baseline`].
+#[derive(Debug)]
+pub struct BaselineFromStrError(String);
+
+impl std::error::Error for BaselineFromStrError {}
+
+impl Display for BaselineFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expecting `last` or a git revision, `{}` is not a commit",
+            self.0
+        )
+    }
+}
+
+/// An error which can be returned when parsing a threshold of a [`Comparison`].
+#[derive(Debug)]
+pub struct ThresholdFromStrError;
+
+impl std::error::Error for ThresholdFromStrError {}
+
+impl Display for ThresholdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a percentage of 0 or more, e.g. `5`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Run> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Run) -> Self {
+        // the timings are stored in the format of `data/timings.json`, with the tags of the run next to them.
+        let mut map = match JsonValue::from(value.timings.clone()) {
+            JsonValue::Object(map) => map,
+            _ => HashMap::new(),
+        };
+
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("dirty".into(), JsonValue::Boolean(value.is_dirty));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let commit = match json.get("commit") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected run.commit to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        let is_dirty = json
+            .get("dirty")
+            .and_then(|v| v.get::<bool>())
+            .copied()
+            .ok_or("Expected run.dirty to be a boolean.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|timestamp| *timestamp as u64)
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let (timings, _) = Timings::from_json(value)?;
+
+        Ok(Run {
+            commit,
+            is_dirty,
+            timestamp,
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Baseline, Delta, History, Run};
+    use crate::{
+        day,
+        template::{
+            timings::{BenchStats, PartTiming, Timing, Timings},
+            PuzzleId,
+        },
+        year,
+    };
+
+    fn part(nanos: f64, stddev: f64) -> PartTiming {
+        PartTiming {
+            nanos,
+            samples: 100,
+            stats: Some(BenchStats {
+                samples: 100,
+                mean: nanos,
+                median: nanos,
+                stddev,
+                ..BenchStats::default()
+            }),
+            heap: None,
+        }
+    }
+
+    fn run(commit: &str, is_dirty: bool, day: u8, part_1: f64, part_2: Option<f64>) -> Run {
+        Run {
+            commit: Some(commit.into()),
+            is_dirty,
+            timestamp: 1_700_000_000,
+            timings: Timings {
+                data: vec![Timing {
+                    year: year!(2024),
+                    day: crate::template::Day::new(day).unwrap(),
+                    parse: None,
+                    part_1: Some(part(part_1, 10.0)),
+                    part_2: part_2.map(|nanos| part(nanos, 10.0)),
+                    part_1_status: None,
+                    part_2_status: None,
+                }],
+                environment: None,
+            },
+        }
+    }
+
+    #[test]
+    fn roundtrips_runs() {
+        let run = run("abc", true, 1, 100.0, Some(200.0));
+        let line = JsonValue::from(&run).stringify().unwrap();
+        assert!(!line.contains('\n'));
+
+        let (history, errors) = History::parse(&format!("{line}\n\nnot json\n"));
+        assert_eq!(errors, vec!["line 3: not valid JSON.".to_string()]);
+        assert_eq!(history.runs.len(), 1);
+
+        let parsed = &history.runs[0];
+        assert_eq!(parsed.commit.as_deref(), Some("abc"));
+        assert!(parsed.is_dirty);
+        assert_eq!(parsed.timestamp, 1_700_000_000);
+        assert_eq!(parsed.timings.data, run.timings.data);
+        assert_eq!(parsed.label(), "abc*");
+    }
+
+    #[test]
+    fn finds_baselines() {
+        let history = History {
+            runs: vec![
+                run("aaa", false, 1, 100.0, Some(200.0)),
+                run("bbb", true, 1, 110.0, None),
+                run("bbb", false, 2, 50.0, None),
+            ],
+        };
+        let day_1 = PuzzleId::new(year!(2024), day!(1));
+
+        let (last, timing) = history.baseline(day_1, 1, &Baseline::Last).unwrap();
+        assert_eq!((last.label().as_str(), timing.nanos), ("bbb*", 110.0));
+
+        // the newest run that timed the part, even if later runs only timed other parts.
+        let (last, _) = history.baseline(day_1, 2, &Baseline::Last).unwrap();
+        assert_eq!(last.label(), "aaa");
+
+        // runs with uncommitted changes do not represent a commit.
+        let commit = Baseline::Commit("bbb".into());
+        assert!(history.baseline(day_1, 1, &commit).is_none());
+        assert!(history.baseline(day_1, 0, &Baseline::Last).is_none());
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let delta = Delta::new(&part(100.0, 3.0), &part(120.0, 4.0));
+        assert_eq!(delta.spread, Some(5.0));
+        assert!((delta.percent() - 20.0).abs() < f64::EPSILON);
+        assert!(delta.is_significant());
+        assert!(delta.is_regression(10.0));
+        assert!(!delta.is_regression(25.0));
+
+        // within the spread of the samples.
+        let noisy = Delta::new(&part(100.0, 30.0), &part(120.0, 40.0));
+        assert!(!noisy.is_significant());
+        assert!(!noisy.is_regression(10.0));
+
+        let faster = Delta::new(&part(100.0, 3.0), &part(80.0, 4.0));
+        assert!(faster.is_significant());
+        assert!(!faster.is_regression(10.0));
+
+        let unknown = Delta::new(&PartTiming::default(), &part(120.0, 4.0));
+        assert_eq!(unknown.spread, None);
+        assert!(!unknown.is_significant());
+    }
+}
@@ -0,0 +1,272 @@
+use std::{collections::HashMap, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::answers::{fingerprint, Answers};
+use crate::template::run_multi::PartAnswers;
+use crate::template::{InputSource, PuzzleId};
+
+/// File in `data/{year}/inputs/{day}/` that holds the known-good answers of its inputs.
+const MANIFEST_FILE: &str = "answers.json";
+
+/// An input that a puzzle is verified against with `verify --all-inputs`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleInput {
+    /// Path of the input, e.g. `data/2024/inputs/17/alice.txt`.
+    pub path: String,
+    /// Fingerprint of the input, see [`fingerprint`].
+    pub id: String,
+    /// The known-good answer of each part, if any.
+    pub answers: PartAnswers,
+}
+
+impl PuzzleInput {
+    /// File name of the input, e.g. `alice.txt`.
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    pub fn source(&self) -> InputSource {
+        InputSource::File(self.path.clone())
+    }
+}
+
+/// A part whose answer does not change with its input.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConstantPart {
+    pub part: u8,
+    pub answer: String,
+    /// Number of distinct inputs the part was run against.
+    pub inputs: usize,
+}
+
+/// Reads the puzzle input and every input in `data/{year}/inputs/{day}/`, e.g. the inputs of other accounts.
+///
+/// The answers of an input are read from the `answers.json` manifest of the directory:
+/// ```json
+/// {
+///   "answers": [
+///     { "file": "alice.txt", "part": 1, "answer": "4,6,3,5,6,3,5,2,1,0" },
+///     { "file": "alice.txt", "part": 2, "answer": 117440 }
+///   ]
+/// }
+/// ```
+/// Parts that are not listed use the accepted answer of the ledger for the fingerprint of the input.
+pub fn read_inputs(puzzle: PuzzleId, ledger: &Answers) -> Result<Vec<PuzzleInput>, String> {
+    let dir = puzzle.inputs_dir();
+    let mut paths = vec![];
+
+    if let Ok(entries) = fs::read_dir(&dir) {
+        for entry in entries {
+            let path = entry
+                .map_err(|e| format!("Failed to read \"{dir}\": {e}"))?
+                .path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                paths.push(path.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    paths.sort();
+
+    if fs::metadata(puzzle.input_path()).is_ok() {
+        paths.insert(0, puzzle.input_path());
+    }
+
+    let manifest = match fs::read_to_string(format!("{dir}/{MANIFEST_FILE}")) {
+        Ok(manifest) => parse_manifest(&manifest)
+            .map_err(|e| format!("Invalid answer manifest \"{dir}/{MANIFEST_FILE}\": {e}"))?,
+        Err(_) => HashMap::new(),
+    };
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let input =
+                fs::read_to_string(&path).map_err(|e| format!("Failed to read \"{path}\": {e}"))?;
+            let id = fingerprint(&input);
+            let name = path.rsplit('/').next().unwrap_or_default().to_string();
+
+            let answers = [1, 2].map(|part| {
+                manifest.get(&(name.clone(), part)).cloned().or_else(|| {
+                    ledger
+                        .correct_answer(puzzle, part, &id)
+                        .map(ToString::to_string)
+                })
+            });
+
+            Ok(PuzzleInput { path, id, answers })
+        })
+        .collect()
+}
+
+/// Parses an answer manifest to the answers of its inputs, keyed by file name and part.
+fn parse_manifest(manifest: &str) -> Result<HashMap<(String, u8), String>, String> {
+    let json = JsonValue::from_str(manifest).or(Err("not valid JSON."))?;
+
+    let entries = json
+        .get::<HashMap<String, JsonValue>>()
+        .and_then(|json| json.get("answers"))
+        .and_then(|answers| answers.get::<Vec<JsonValue>>())
+        .ok_or("expected an object with an `answers` array.")?;
+
+    let mut answers = HashMap::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let error = |message: &str| format!("answer {}: {message}", index + 1);
+
+        let entry = entry
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or_else(|| error("expected an object."))?;
+
+        let file = entry
+            .get("file")
+            .and_then(|file| file.get::<String>())
+            .ok_or_else(|| error("expected `file` to be a string."))?;
+
+        let part = match entry.get("part").and_then(|part| part.get::<f64>()) {
+            Some(part) if *part == 1.0 => 1,
+            Some(part) if *part == 2.0 => 2,
+            _ => return Err(error("expected `part` to be 1 or 2.")),
+        };
+
+        // numbers are floats, so answers above 2^53 have to be written as strings.
+        #[allow(clippy::cast_possible_truncation)]
+        let answer = match entry.get("answer") {
+            Some(JsonValue::String(answer)) => answer.clone(),
+            Some(JsonValue::Number(n)) if n.fract() == 0.0 => format!("{}", *n as i64),
+            _ => return Err(error("expected `answer` to be a string or an integer.")),
+        };
+
+        answers.insert((file.clone(), part), answer);
+    }
+
+    Ok(answers)
+}
+
+/// Finds the parts that return the same answer for different inputs, which usually means that the solution
+/// ignores its input, e.g. because it hardcodes values of one account. `answers` holds the answers of each input.
+/// A constant answer is only accepted if it is the known-good answer of every input.
+pub fn constant_parts(inputs: &[PuzzleInput], answers: &[PartAnswers]) -> Vec<ConstantPart> {
+    let mut ids: Vec<&str> = inputs.iter().map(|input| input.id.as_str()).collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    if ids.len() < 2 {
+        return vec![];
+    }
+
+    (0..2)
+        .filter_map(|index| {
+            let first = answers.first()?[index].as_ref()?;
+            let is_constant = answers.iter().all(|a| a[index].as_ref() == Some(first));
+            let is_expected = inputs
+                .iter()
+                .all(|input| input.answers[index].as_ref() == Some(first));
+
+            (is_constant && !is_expected).then(|| ConstantPart {
+                part: index as u8 + 1,
+                answer: first.clone(),
+                inputs: ids.len(),
+            })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{constant_parts, parse_manifest, ConstantPart, PuzzleInput};
+
+    fn input(name: &str, id: &str, answers: [Option<&str>; 2]) -> PuzzleInput {
+        PuzzleInput {
+            path: format!("data/2024/inputs/17/{name}"),
+            id: id.into(),
+            answers: answers.map(|a| a.map(ToString::to_string)),
+        }
+    }
+
+    fn answers(part_1: Option<&str>, part_2: Option<&str>) -> [Option<String>; 2] {
+        [
+            part_1.map(ToString::to_string),
+            part_2.map(ToString::to_string),
+        ]
+    }
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = parse_manifest(
+            r#"{
+                "answers": [
+                    { "file": "alice.txt", "part": 1, "answer": "4,6,3,5" },
+                    { "file": "alice.txt", "part": 2, "answer": 117440 }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.len(), 2);
+        assert_eq!(manifest[&("alice.txt".into(), 1)], "4,6,3,5");
+        assert_eq!(manifest[&("alice.txt".into(), 2)], "117440");
+
+        assert_eq!(
+            parse_manifest(r#"{ "answers": [{ "file": "alice.txt", "part": 3, "answer": 1 }] }"#),
+            Err("answer 1: expected `part` to be 1 or 2.".into())
+        );
+        assert!(parse_manifest(r#"{ "data": [] }"#).is_err());
+    }
+
+    #[test]
+    fn names_inputs() {
+        let input = input("alice.txt", "a", [None, None]);
+        assert_eq!(input.name(), "alice.txt");
+        assert_eq!(
+            input.source().to_string(),
+            "`data/2024/inputs/17/alice.txt`"
+        );
+    }
+
+    #[test]
+    fn finds_constant_parts() {
+        let inputs = [
+            input("17.txt", "a", [Some("1,2"), Some("42")]),
+            input("alice.txt", "b", [Some("3,4"), Some("43")]),
+        ];
+
+        let results = constant_parts(
+            &inputs,
+            &[
+                answers(Some("1,2"), Some("42")),
+                answers(Some("3,4"), Some("42")),
+            ],
+        );
+        assert_eq!(
+            results,
+            vec![ConstantPart {
+                part: 2,
+                answer: "42".into(),
+                inputs: 2
+            }]
+        );
+
+        // unsolved parts are not constant.
+        assert!(constant_parts(&inputs, &[answers(None, None), answers(None, None)]).is_empty());
+    }
+
+    #[test]
+    fn accepts_expected_constant_parts() {
+        // a constant answer is fine if every input is known to have it.
+        let inputs = [
+            input("17.txt", "a", [None, Some("42")]),
+            input("alice.txt", "b", [None, Some("42")]),
+        ];
+        let results = [answers(None, Some("42")), answers(None, Some("42"))];
+        assert!(constant_parts(&inputs, &results).is_empty());
+
+        // copies of the same input are expected to have the same answers.
+        let inputs = [
+            input("17.txt", "a", [None, None]),
+            input("alice.txt", "a", [None, None]),
+        ];
+        assert!(constant_parts(&inputs, &results).is_empty());
+    }
+}
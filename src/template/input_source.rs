@@ -0,0 +1,168 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use crate::template::PuzzleId;
+
+/// Where a solution reads its input from. Selected with the `--input`, `--example`, `--example-part`,
+/// `--example-file` and `--stdin` arguments of `solve`, and passed on to the solution binary.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/{year}/inputs`.
+    #[default]
+    Puzzle,
+    /// The example in `data/{year}/examples`, or one of its numbered variants, e.g. `03-2.txt` or `03_2.txt`.
+    Example(Option<u8>),
+    /// A named file in `data/{year}/examples`, e.g. `04-part_two.txt`.
+    ExampleFile(String),
+    File(String),
+    Stdin,
+}
+
+impl InputSource {
+    /// Read the input source from the arguments of a solution binary.
+    pub fn from_args(args: &[String]) -> Self {
+        let value_of = |flag: &str| {
+            args.windows(2)
+                .find(|pair| pair[0] == flag)
+                .map(|pair| pair[1].clone())
+        };
+
+        if args.iter().any(|x| x == "--stdin") {
+            InputSource::Stdin
+        } else if let Some(path) = value_of("--input") {
+            InputSource::File(path)
+        } else if let Some(file) = value_of("--example-file") {
+            InputSource::ExampleFile(file)
+        } else if let Some(part) = value_of("--example-part").and_then(|p| p.parse().ok()) {
+            InputSource::Example(Some(part))
+        } else if args.iter().any(|x| x == "--example") {
+            InputSource::Example(None)
+        } else {
+            InputSource::Puzzle
+        }
+    }
+
+    /// Arguments that pass this input source on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(part)) => vec!["--example-part".into(), part.to_string()],
+            InputSource::ExampleFile(file) => vec!["--example-file".into(), file.clone()],
+            InputSource::File(path) => vec!["--input".into(), path.clone()],
+            InputSource::Stdin => vec!["--stdin".into()],
+        }
+    }
+
+    /// Only answers for the puzzle input can be submitted.
+    pub fn is_canonical(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    /// The path of the file this source reads from, if any.
+    /// A numbered example is read from `{day}-{part}.txt`, or from `{day}_{part}.txt` if only that exists.
+    pub fn path(&self, puzzle: PuzzleId) -> Option<String> {
+        let examples = format!("data/{}/examples", puzzle.year);
+        match self {
+            InputSource::Puzzle => Some(puzzle.input_path()),
+            InputSource::Example(None) => Some(puzzle.example_path()),
+            InputSource::Example(Some(part)) => {
+                let dashed = format!("{examples}/{}-{part}.txt", puzzle.day);
+                let underscored = format!("{examples}/{}_{part}.txt", puzzle.day);
+                if Path::new(&dashed).exists() || !Path::new(&underscored).exists() {
+                    Some(dashed)
+                } else {
+                    Some(underscored)
+                }
+            }
+            InputSource::ExampleFile(file) => Some(format!("{examples}/{file}")),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    pub fn read(&self, puzzle: PuzzleId) -> io::Result<String> {
+        match self.path(puzzle) {
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "the puzzle input"),
+            InputSource::Example(None) => write!(f, "the example"),
+            InputSource::Example(Some(part)) => write!(f, "example {part}"),
+            InputSource::ExampleFile(file) => write!(f, "example `{file}`"),
+            InputSource::File(path) => write!(f, "`{path}`"),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+    use crate::{day, template::PuzzleId, year};
+
+    #[test]
+    fn roundtrips_input_sources_through_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some(2)),
+            InputSource::ExampleFile("04-part_two.txt".into()),
+            InputSource::File("other.txt".into()),
+            InputSource::Stdin,
+        ] {
+            let mut args = vec!["--time".to_string()];
+            args.extend(source.to_args());
+            assert_eq!(InputSource::from_args(&args), source);
+        }
+    }
+
+    #[test]
+    fn resolves_paths() {
+        let puzzle = PuzzleId::new(year!(2024), day!(3));
+        assert_eq!(
+            InputSource::Puzzle.path(puzzle).as_deref(),
+            Some("data/2024/inputs/03.txt")
+        );
+        assert_eq!(
+            InputSource::Example(Some(3)).path(puzzle).as_deref(),
+            Some("data/2024/examples/03-3.txt")
+        );
+        assert_eq!(
+            InputSource::ExampleFile("04-part_two.txt".into())
+                .path(puzzle)
+                .as_deref(),
+            Some("data/2024/examples/04-part_two.txt")
+        );
+        assert_eq!(InputSource::Stdin.path(puzzle), None);
+        assert!(InputSource::Puzzle.is_canonical());
+        assert!(!InputSource::Example(None).is_canonical());
+    }
+
+    #[test]
+    fn reads_numbered_examples_with_underscores() {
+        let puzzle = PuzzleId::new(year!(2024), day!(3));
+        let source = InputSource::Example(Some(2));
+        assert_eq!(
+            source.path(puzzle).as_deref(),
+            Some("data/2024/examples/03_2.txt")
+        );
+        assert!(source.read(puzzle).unwrap().contains("don't()"));
+    }
+}
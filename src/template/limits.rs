@@ -0,0 +1,130 @@
+/// Wall-clock and memory limits for solution parts.
+use std::{env, fmt::Display, str::FromStr, time::Duration};
+
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+
+/// Limits that apply to each part of a solution. A part that exceeds one of them is aborted.
+/// Configured with `AOC_TIMEOUT` (seconds) and `AOC_MEMORY_LIMIT` (MiB), or with the `--timeout`
+/// and `--memory-limit` flags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub timeout: Option<Duration>,
+    /// Peak heap usage in bytes.
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    /// Read the limits from the `AOC_TIMEOUT` and `AOC_MEMORY_LIMIT` environment variables.
+    /// Invalid values are ignored.
+    pub fn from_env() -> Self {
+        Limits {
+            timeout: env::var("AOC_TIMEOUT")
+                .ok()
+                .and_then(|s| Limits::parse_timeout(&s).ok()),
+            memory: env::var("AOC_MEMORY_LIMIT")
+                .ok()
+                .and_then(|s| Limits::parse_memory(&s).ok()),
+        }
+    }
+
+    /// Read the limits from the environment, overridden by the `--timeout` and `--memory-limit` arguments.
+    pub fn from_args(args: &[String]) -> Self {
+        let mut limits = Limits::from_env();
+        for pair in args.windows(2) {
+            match pair[0].as_str() {
+                "--timeout" => {
+                    limits.timeout = Limits::parse_timeout(&pair[1]).ok().or(limits.timeout);
+                }
+                "--memory-limit" => {
+                    limits.memory = Limits::parse_memory(&pair[1]).ok().or(limits.memory);
+                }
+                _ => {}
+            }
+        }
+        limits
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.timeout.is_none() && self.memory.is_none()
+    }
+
+    /// Arguments that pass these limits on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(timeout) = self.timeout {
+            args.extend(["--timeout".into(), timeout.as_secs_f64().to_string()]);
+        }
+        if let Some(memory) = self.memory {
+            args.extend([
+                "--memory-limit".into(),
+                (memory / BYTES_PER_MIB).to_string(),
+            ]);
+        }
+        args
+    }
+
+    /// Parse a timeout in (fractional) seconds, e.g. `2.5`.
+    pub fn parse_timeout(s: &str) -> Result<Duration, LimitFromStrError> {
+        f64::from_str(s)
+            .ok()
+            .filter(|secs| *secs > 0.0)
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .ok_or(LimitFromStrError("a timeout in seconds"))
+    }
+
+    /// Parse a memory limit in MiB, e.g. `512`.
+    pub fn parse_memory(s: &str) -> Result<u64, LimitFromStrError> {
+        u64::from_str(s)
+            .ok()
+            .filter(|mib| *mib > 0)
+            .and_then(|mib| mib.checked_mul(BYTES_PER_MIB))
+            .ok_or(LimitFromStrError("a memory limit in MiB"))
+    }
+}
+
+/// Formats a number of bytes as MiB, e.g. `512 MiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_mib(bytes: u64) -> String {
+    format!("{:.0} MiB", bytes as f64 / BYTES_PER_MIB as f64)
+}
+
+#[derive(Debug)]
+pub struct LimitFromStrError(&'static str);
+
+impl std::error::Error for LimitFromStrError {}
+
+impl Display for LimitFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting {} greater than 0", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Limits;
+
+    #[test]
+    fn parses_limits() {
+        assert_eq!(
+            Limits::parse_timeout("2.5").unwrap(),
+            Duration::from_millis(2500)
+        );
+        assert_eq!(Limits::parse_memory("512").unwrap(), 512 * 1024 * 1024);
+        assert!(Limits::parse_timeout("0").is_err());
+        assert!(Limits::parse_timeout("-1").is_err());
+        assert!(Limits::parse_memory("1.5").is_err());
+    }
+
+    #[test]
+    fn roundtrips_limits_through_args() {
+        let limits = Limits {
+            timeout: Some(Duration::from_millis(1500)),
+            memory: Some(64 * 1024 * 1024),
+        };
+        assert_eq!(Limits::from_args(&limits.to_args()), limits);
+    }
+}
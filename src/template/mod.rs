@@ -1,15 +1,40 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
 pub mod runner;
 
+pub use alloc::CountingAlloc;
 pub use day::*;
+pub use day_filter::DayFilters;
+pub use history::{Baseline, Comparison};
+pub use input_source::InputSource;
+pub use limits::Limits;
+pub use parse_error::*;
+pub use puzzle_id::*;
+pub use report::ExportFormat;
+pub use scaffold_template::ScaffoldTemplate;
+pub use year::*;
 
+mod alloc;
+mod answers;
 mod day;
+mod day_filter;
+mod environment;
+mod history;
+mod input_set;
+mod input_source;
+mod limits;
+mod parse_error;
+mod puzzle_examples;
+mod puzzle_id;
 mod readme_benchmarks;
+mod record;
+mod report;
 mod run_multi;
+mod scaffold_template;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -17,52 +42,144 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
+        .join(puzzle.year.to_string())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Helper function that reads a file from the examples folder of a puzzle, e.g. `03_2.txt`.
+/// Used by the tests generated from example manifests.
+#[must_use]
+pub fn read_example(puzzle: PuzzleId, file: &str) -> String {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd
+        .join("data")
+        .join(puzzle.year.to_string())
+        .join("examples")
+        .join(file);
+    let f = fs::read_to_string(filepath);
+    f.expect("could not open example file")
+}
+
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// The binary reads the puzzle input, unless its arguments select another [`InputSource`].
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional parameter `parse = <fn>` adds a parse phase: the function is called once with the input,
+/// and `part_one` and `part_two` receive a reference to its output. Parsing is timed separately.
+///
+/// The optional, last parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// The tests of the example manifest `data/{year}/examples/{day}.json` are added to the tests of the solution.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@impl $year, $day, [], [part_one, 1] [part_two, 2]);
+    };
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, [], [part_one, 1]);
+    };
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, [], [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $year, $day, [$parse], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl $year, $day, [$parse], [part_one, 1]);
     };
+    ($year:expr, $day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl $year, $day, [$parse], [part_two, 2]);
+    };
+
+    (@impl $year:expr, $day:expr, [$($parse:expr)?], $( [$func:expr, $part:expr] )*) => {
+        /// The year of the current puzzle.
+        #[allow(dead_code)]
+        const YEAR: $crate::template::Year = $crate::year!($year);
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        #[allow(dead_code)]
+        const DAY: $crate::template::Day = $crate::day!($day, $year);
+
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        // counts the heap usage for `--memory` and memory limits. The main binary, which includes every
+        // solution with the `in-process` feature, does not count it.
+        #[cfg(all(feature = "count-alloc", not(feature = "dhat-heap"), not(feature = "in-process")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::CountingAlloc = $crate::template::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            // with limits, every phase runs in a process of its own that is stopped when it exceeds them.
+            if is_isolated() {
+                run_isolated(PUZZLE, $crate::solution!(@has_parse [$($parse)?]), &[$($part),*]);
+                return;
+            }
+            let input = read_input(PUZZLE);
+            let input = input.as_str();
+            $crate::solution!(@main input, [$($parse)?], $( [$func, $part] )*);
+        }
+
+        /// The entry of the current day in the solution registry.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            puzzle: PUZZLE,
+            run: |input, options| {
+                use $crate::template::runner::*;
+                $crate::solution!(@run input, options, [$($parse)?], $( [$func, $part] )*)
+            },
+        };
+
+        /// Tests generated from the example manifest of the current day, see `build.rs`.
+        #[cfg(test)]
+        mod examples {
+            #[allow(unused_imports)]
+            use super::*;
+            include!(concat!(env!("OUT_DIR"), "/examples/", $year, "_", $day, ".rs"));
         }
     };
+
+    (@has_parse []) => { false };
+    (@has_parse [$parse:expr]) => { true };
+
+    (@main $input:ident, [], $( [$func:expr, $part:expr] )*) => {
+        $( run_part($func, $input, PUZZLE, $part); )*
+    };
+    (@main $input:ident, [$parse:expr], $( [$func:expr, $part:expr] )*) => {
+        if let Some(parsed) = run_parse($parse, $input) {
+            $( run_parsed_part($func, &parsed, $input, PUZZLE, $part); )*
+        }
+    };
+
+    (@run $input:ident, $options:ident, [], $( [$func:expr, $part:expr] )*) => {
+        [$( run_part_record($func, $input, $part, $options), )*].into_iter().flatten().collect()
+    };
+    (@run $input:ident, $options:ident, [$parse:expr], $( [$func:expr, $part:expr] )*) => {{
+        let (parsed, record) = run_parse_record($parse, $input, $options);
+        let mut records = vec![record];
+        if let Some(parsed) = parsed {
+            $( records.extend(run_part_record($func, &parsed, $part, $options)); )*
+        }
+        records
+    }};
 }
@@ -0,0 +1,99 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// An error in the puzzle input, with the position it was found at.
+/// Lines and columns start at 1, columns count characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error at the start of `span`, which has to be a slice of `input`.
+    /// Spans from other strings are reported at the end of the input.
+    pub fn at(input: &str, span: &str, message: impl Into<String>) -> Self {
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| *offset <= input.len())
+            .unwrap_or(input.len());
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            message,
+        )
+    }
+
+    /// Creates an error at the end of `input`, e.g. for missing lines.
+    pub fn at_end(input: &str, message: impl Into<String>) -> Self {
+        ParseError::at(input, &input[input.len()..], message)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `span`, a slice of `input`, reporting its position if it is not valid.
+///
+/// # Errors
+///
+/// Returns a [`ParseError`] at the start of `span` if it cannot be parsed as `T`.
+pub fn parse_at<T: FromStr>(input: &str, span: &str) -> Result<T, ParseError>
+where
+    T::Err: Display,
+{
+    span.parse()
+        .map_err(|e| ParseError::at(input, span, format!("invalid value `{span}`: {e}")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_at, ParseError};
+
+    #[test]
+    fn locates_spans() {
+        let input = "Button A: X+94\nButton B: X+2ä, Y+67";
+        let span = &input[25..28];
+        assert_eq!(ParseError::at(input, span, "").line, 2);
+        assert_eq!(ParseError::at(input, span, "").column, 11);
+        assert_eq!(
+            ParseError::at_end(input, "missing prize").to_string(),
+            "line 2, column 21: missing prize"
+        );
+    }
+
+    #[test]
+    fn parses_spans() {
+        let input = "Register A: 729\nRegister B: x";
+        assert_eq!(parse_at::<u32>(input, &input[12..15]), Ok(729));
+        assert_eq!(
+            parse_at::<u32>(input, &input[28..])
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 13: invalid value `x`: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,221 @@
+/// The examples of a puzzle, extracted from its markdown description in `data/{year}/puzzles`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    /// Every code block of the description, in document order.
+    pub blocks: Vec<String>,
+    /// The example of each part, if one could be identified.
+    pub parts: [Option<PartExample>; 2],
+}
+
+/// The example input of a part and its expected answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartExample {
+    /// Index into [`PuzzleExamples::blocks`].
+    pub block: usize,
+    pub answer: Option<String>,
+}
+
+/// A paragraph or code block of a puzzle description.
+#[derive(Debug, PartialEq, Eq)]
+enum Block<'a> {
+    Text(&'a str),
+    /// Index of the code block.
+    Code(usize),
+}
+
+const PART_TWO_HEADING: &str = "## --- Part Two ---";
+
+impl PuzzleExamples {
+    /// Extracts the examples from a puzzle description.
+    ///
+    /// The example of part one is the first code block of the description. Part two reuses it,
+    /// unless its description introduces a new example. The expected answer of a part is the last
+    /// inline code span before the final question of its description, e.g. "a total distance of `11`".
+    pub fn parse(markdown: &str) -> Self {
+        let mut blocks: Vec<String> = vec![];
+        let mut sections: [Vec<Block>; 2] = [vec![], vec![]];
+        let mut section = 0;
+        let mut code: Option<String> = None;
+        let mut paragraph_start: Option<usize> = None;
+
+        let mut offset = 0;
+        for line in markdown.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+
+            if let Some(block) = code.as_mut() {
+                if line.trim_end() == "```" {
+                    blocks.push(code.take().unwrap_or_default());
+                    sections[section].push(Block::Code(blocks.len() - 1));
+                } else {
+                    block.push_str(line);
+                }
+                continue;
+            }
+
+            let is_break = line.trim().is_empty() || line.trim_end() == "```";
+            if is_break || line.starts_with(PART_TWO_HEADING) {
+                if let Some(paragraph) = paragraph_start.take() {
+                    sections[section].push(Block::Text(markdown[paragraph..start].trim()));
+                }
+            }
+
+            if line.starts_with(PART_TWO_HEADING) {
+                section = 1;
+            } else if line.trim_end() == "```" {
+                code = Some(String::new());
+            } else if !is_break && paragraph_start.is_none() {
+                paragraph_start = Some(start);
+            }
+        }
+
+        if let Some(paragraph) = paragraph_start {
+            sections[section].push(Block::Text(markdown[paragraph..].trim()));
+        }
+
+        let part_one = first_code_block(&sections[0]).map(|block| PartExample {
+            block,
+            answer: find_answer(&sections[0]),
+        });
+
+        let part_two = if sections[1].is_empty() {
+            None
+        } else {
+            new_example(&sections[1], &blocks)
+                .or(part_one.as_ref().map(|p| p.block))
+                .map(|block| PartExample {
+                    block,
+                    answer: find_answer(&sections[1]),
+                })
+        };
+
+        PuzzleExamples {
+            blocks,
+            parts: [part_one, part_two],
+        }
+    }
+}
+
+fn first_code_block(section: &[Block]) -> Option<usize> {
+    section.iter().find_map(|block| match block {
+        Block::Code(index) => Some(*index),
+        Block::Text(_) => None,
+    })
+}
+
+/// A code block of part two that is introduced as an example and differs from all earlier blocks.
+fn new_example(section: &[Block], blocks: &[String]) -> Option<usize> {
+    section.windows(2).find_map(|pair| match pair {
+        [Block::Text(text), Block::Code(index)]
+            if text.to_lowercase().contains("example")
+                && !blocks[..*index].contains(&blocks[*index]) =>
+        {
+            Some(*index)
+        }
+        _ => None,
+    })
+}
+
+/// The last single-word code span before the question that ends the description of a part.
+fn find_answer(section: &[Block]) -> Option<String> {
+    let paragraphs: Vec<&str> = section
+        .iter()
+        .filter_map(|block| match block {
+            Block::Text(text) if !text.contains("Your puzzle answer was") => Some(*text),
+            _ => None,
+        })
+        .collect();
+
+    let (_question, explanation) = paragraphs.split_last()?;
+    explanation
+        .iter()
+        .rev()
+        .find_map(|paragraph| {
+            code_spans(paragraph)
+                .into_iter()
+                .rev()
+                .find(|span| is_answer(span))
+        })
+        .map(str::to_string)
+}
+
+fn code_spans(paragraph: &str) -> Vec<&str> {
+    paragraph.split('`').skip(1).step_by(2).collect()
+}
+
+fn is_answer(span: &str) -> bool {
+    !span.is_empty() && !span.contains(char::is_whitespace)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartExample, PuzzleExamples};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Pair up the `smallest` numbers.
+
+In the example above, this is `2 + 1`, a total distance of `11`!
+
+What is the total distance between your lists?
+
+Your puzzle answer was `1189304`.
+
+## --- Part Two ---
+
+For these example lists, here is the process:
+
+```
+3   4
+4   3
+```
+
+So, the similarity score at the end of this process is `31` (`9 + 4`).
+
+Once again consider your left and right lists. What is their similarity score?
+";
+
+    #[test]
+    fn extracts_examples() {
+        let examples = PuzzleExamples::parse(PUZZLE);
+        assert_eq!(examples.blocks, vec!["3   4\n4   3\n"; 2]);
+        assert_eq!(
+            examples.parts,
+            [
+                Some(PartExample {
+                    block: 0,
+                    answer: Some("11".into())
+                }),
+                Some(PartExample {
+                    block: 0,
+                    answer: Some("31".into())
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_new_examples_of_part_two() {
+        let puzzle = PUZZLE.replace("```\n3   4\n4   3\n```\n\nSo", "```\n1\n```\n\nSo");
+        let examples = PuzzleExamples::parse(&puzzle);
+        assert_eq!(examples.blocks[1], "1\n");
+        assert_eq!(examples.parts[1].as_ref().map(|p| p.block), Some(1));
+    }
+
+    #[test]
+    fn handles_unsolved_descriptions() {
+        let examples =
+            PuzzleExamples::parse("## --- Day 2: Red-Nosed Reports ---\n\nNo examples.\n");
+        assert!(examples.blocks.is_empty());
+        assert_eq!(examples.parts, [None, None]);
+    }
+}
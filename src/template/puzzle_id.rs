@@ -0,0 +1,81 @@
+use std::fmt::Display;
+
+use crate::template::{Day, Year};
+
+/// Identifies a puzzle by its year and day.
+/// All file paths of a puzzle are derived from this value.
+///
+/// # Display
+/// This value displays as `{year}_{day}`, which is also the name of the solution binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of a data file, e.g. `data/2024/inputs/01.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> String {
+        format!("data/{}/{folder}/{}.{extension}", self.year, self.day)
+    }
+
+    pub fn input_path(self) -> String {
+        self.data_path("inputs", "txt")
+    }
+
+    /// Directory of further inputs of the puzzle, e.g. the inputs of other accounts: `data/2024/inputs/01`.
+    pub fn inputs_dir(self) -> String {
+        format!("data/{}/inputs/{}", self.year, self.day)
+    }
+
+    pub fn example_path(self) -> String {
+        self.data_path("examples", "txt")
+    }
+
+    /// Path of the example manifest, e.g. `data/2024/examples/01.json`. See `build.rs`.
+    pub fn manifest_path(self) -> String {
+        self.data_path("examples", "json")
+    }
+
+    pub fn puzzle_path(self) -> String {
+        self.data_path("puzzles", "md")
+    }
+
+    /// Name of the solution binary, e.g. `2024_01`.
+    pub fn bin_name(self) -> String {
+        self.to_string()
+    }
+
+    /// Path of the solution module, e.g. `./src/bin/2024_01.rs`.
+    pub fn bin_path(self) -> String {
+        format!("./src/bin/{self}.rs")
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.year, self.day)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::{day, year};
+
+    #[test]
+    fn derives_paths() {
+        let puzzle = PuzzleId::new(year!(2024), day!(1));
+        assert_eq!(puzzle.to_string(), "2024_01");
+        assert_eq!(puzzle.input_path(), "data/2024/inputs/01.txt");
+        assert_eq!(puzzle.inputs_dir(), "data/2024/inputs/01");
+        assert_eq!(puzzle.example_path(), "data/2024/examples/01.txt");
+        assert_eq!(puzzle.manifest_path(), "data/2024/examples/01.json");
+        assert_eq!(puzzle.puzzle_path(), "data/2024/puzzles/01.md");
+        assert_eq!(puzzle.bin_path(), "./src/bin/2024_01.rs");
+    }
+}
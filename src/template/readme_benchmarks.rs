@@ -1,9 +1,12 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io, str::FromStr};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{
+    record::PartStatus,
+    runner::{format_bytes, format_nanos},
+    timings::{HeapStats, PartTiming, Timing, Timings},
+};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -25,11 +28,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -52,27 +50,151 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Optional columns of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Duration of the parse phase.
+    Parse,
+    /// Number of samples of both parts.
+    Samples,
+    /// Share of a day in the total runtime of its year.
+    Share,
+    /// Heap usage, shown for years that have been profiled with `cargo time --memory`.
+    Memory,
+}
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+/// Layout of the benchmark table. Configured with `AOC_BENCHMARK_COLUMNS`, e.g. `parse,samples,share,memory`,
+/// and `AOC_BENCHMARK_CHART`, which adds a bar chart that ranks the days of a year by their runtime.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableLayout {
+    pub columns: Vec<Column>,
+    pub chart: bool,
+}
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+impl Default for TableLayout {
+    fn default() -> Self {
+        TableLayout {
+            columns: vec![Column::Parse, Column::Memory],
+            chart: false,
+        }
+    }
+}
+
+impl TableLayout {
+    /// Read the layout from the environment. Unknown columns are reported and skipped.
+    pub fn from_env() -> Self {
+        let mut layout = TableLayout::default();
+
+        if let Ok(columns) = env::var("AOC_BENCHMARK_COLUMNS") {
+            layout.columns = columns
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .filter_map(|name| match Column::from_str(name) {
+                    Ok(column) => Some(column),
+                    Err(e) => {
+                        eprintln!("Ignoring benchmark column: {e}");
+                        None
+                    }
+                })
+                .collect();
+        }
+
+        if let Ok(chart) = env::var("AOC_BENCHMARK_CHART") {
+            layout.chart = matches!(chart.as_str(), "1" | "true");
+        }
+
+        layout
+    }
+
+    fn has(&self, column: Column) -> bool {
+        self.columns.contains(&column)
+    }
+
+    /// The columns of the table of a year, after the day.
+    /// Heap usage is only shown for years that have been profiled with `cargo time --memory`.
+    pub(crate) fn year_columns(&self, days: &[&Timing]) -> Vec<TableColumn> {
+        let has_heap = self.has(Column::Memory) && days.iter().any(|t| t.heap().is_some());
+
+        let mut columns = vec![];
+        if self.has(Column::Parse) {
+            columns.push(TableColumn::Parse);
+        }
+        columns.extend([TableColumn::Part(1), TableColumn::Part(2)]);
+        if self.has(Column::Samples) {
+            columns.push(TableColumn::Samples);
+        }
+        if self.has(Column::Share) {
+            columns.push(TableColumn::Share);
+        }
+        if has_heap {
+            columns.push(TableColumn::Memory);
+        }
+        columns
+    }
+}
+
+/// A column of the table of a year. The memory column spans peak heap, allocations and allocated bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TableColumn {
+    Parse,
+    Part(u8),
+    Samples,
+    Share,
+    Memory,
+}
+
+impl TableColumn {
+    pub(crate) fn headers(self) -> Vec<&'static str> {
+        match self {
+            TableColumn::Parse => vec!["Parse"],
+            TableColumn::Part(1) => vec!["Part 1"],
+            TableColumn::Part(_) => vec!["Part 2"],
+            TableColumn::Samples => vec!["Samples"],
+            TableColumn::Share => vec!["Share"],
+            TableColumn::Memory => vec!["Peak heap", "Allocations", "Allocated"],
+        }
+    }
+
+    /// The cells of a day in this column, as plain text. `year_nanos` is the total runtime of the year.
+    pub(crate) fn cells(self, timing: &Timing, year_nanos: f64) -> Vec<String> {
+        match self {
+            TableColumn::Parse => vec![format_part(timing.parse.as_ref(), None)],
+            TableColumn::Part(1) => vec![format_part(timing.part_1.as_ref(), timing.part_1_status)],
+            TableColumn::Part(_) => vec![format_part(timing.part_2.as_ref(), timing.part_2_status)],
+            TableColumn::Samples => vec![format_samples(timing)],
+            TableColumn::Share => vec![format_share(timing.total_nanos(), year_nanos)],
+            TableColumn::Memory => format_heap(timing.heap().as_ref()).into(),
+        }
     }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "samples" => Ok(Column::Samples),
+            "share" => Ok(Column::Share),
+            "memory" => Ok(Column::Memory),
+            _ => Err(format!(
+                "unknown column `{s}`, expecting one of parse, samples, share and memory."
+            )),
+        }
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    layout: &TableLayout,
+) -> String {
+    let header = format!("{prefix} Benchmarks");
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header];
+    lines.extend(construct_tables(prefix, &timings, layout));
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
@@ -81,9 +203,146 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+/// The environment of the timings and a table for every year, with headings one level below `prefix`.
+pub(crate) fn construct_tables(
+    prefix: &str,
+    timings: &Timings,
+    layout: &TableLayout,
+) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+
+    if let Some(environment) = &timings.environment {
+        lines.extend([String::new(), format!("_Measured on {environment}._")]);
+    }
+
+    // one table per year, newest first.
+    for year in timings.years().into_iter().rev() {
+        let days: Vec<&Timing> = timings.data.iter().filter(|t| t.year == year).collect();
+        let year_nanos: f64 = days.iter().map(|t| t.total_nanos()).sum();
+        let columns = layout.year_columns(&days);
+
+        let header: Vec<&str> = ["Day"]
+            .into_iter()
+            .chain(columns.iter().flat_map(|c| c.headers()))
+            .collect();
+
+        lines.extend([
+            String::new(),
+            format!("{prefix}# {year}"),
+            String::new(),
+            format!("| {} |", header.join(" | ")),
+            format!("|{}", " :---: |".repeat(header.len())),
+        ]);
+
+        for timing in &days {
+            let day = format!(
+                "[Day {}]({})",
+                timing.day.into_inner(),
+                timing.puzzle().bin_path()
+            );
+            let cells: Vec<String> = columns
+                .iter()
+                .flat_map(|column| column.cells(timing, year_nanos))
+                .map(|cell| format!("`{cell}`"))
+                .collect();
+
+            lines.push(format!("| {day} | {} |", cells.join(" | ")));
+        }
+
+        if layout.chart {
+            lines.push(String::new());
+            lines.extend(construct_chart(&days));
+        }
+    }
+
+    lines
+}
+
+/// A text bar chart of the days of a year, ranked by their total runtime. Days without a timing are left out.
+fn construct_chart(days: &[&Timing]) -> Vec<String> {
+    const WIDTH: f64 = 30.0;
+    const EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+    let ranked = rank_days(days);
+
+    let Some(max) = ranked.first().map(|(_, nanos)| *nanos) else {
+        return vec![];
+    };
+
+    let mut lines = vec!["```text".to_string()];
+    for (timing, nanos) in ranked {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let eighths = ((nanos / max * WIDTH * 8.0).round() as usize).max(1);
+        let bar = format!("{}{}", "█".repeat(eighths / 8), EIGHTHS[eighths % 8]);
+        lines.push(format!(
+            "Day {:>2} {bar:<30} {}",
+            timing.day.into_inner(),
+            format_nanos(nanos)
+        ));
+    }
+    lines.push("```".to_string());
+
+    lines
+}
+
+/// Days with a timing and their total runtime, slowest first.
+pub(crate) fn rank_days<'a>(days: &[&'a Timing]) -> Vec<(&'a Timing, f64)> {
+    let mut ranked: Vec<(&Timing, f64)> = days
+        .iter()
+        .map(|t| (*t, t.total_nanos()))
+        .filter(|(_, nanos)| *nanos > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.puzzle().cmp(&b.0.puzzle())));
+    ranked
+}
+
+/// Prefer the median over the mean if sample statistics are present, as it is less prone to noise.
+/// Parts without a timing show why they were aborted, e.g. `TIMEOUT`.
+fn format_part(timing: Option<&PartTiming>, status: Option<PartStatus>) -> String {
+    match timing {
+        Some(timing) => format_nanos(timing.stats.as_ref().map_or(timing.nanos, |s| s.median)),
+        None => status.map_or("-", PartStatus::label).into(),
+    }
+}
+
+/// The number of samples of both parts, e.g. `1443 / 12`.
+fn format_samples(timing: &Timing) -> String {
+    let samples = |part: Option<&PartTiming>| part.map_or("-".into(), |p| p.samples.to_string());
+    format!(
+        "{} / {}",
+        samples(timing.part_1.as_ref()),
+        samples(timing.part_2.as_ref())
+    )
+}
+
+fn format_share(nanos: f64, total_nanos: f64) -> String {
+    if total_nanos > 0.0 {
+        format!("{:.1}%", nanos / total_nanos * 100.0)
+    } else {
+        "-".into()
+    }
+}
+
+/// The heap usage columns of a day: peak, allocations and allocated bytes.
+fn format_heap(heap: Option<&HeapStats>) -> [String; 3] {
+    match heap {
+        Some(heap) => [
+            format_bytes(heap.peak_bytes),
+            heap.allocations.to_string(),
+            format_bytes(heap.allocated_bytes),
+        ],
+        None => ["-".into(), "-".into(), "-".into()],
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    layout: &TableLayout,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", timings, total_millis, layout);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
@@ -92,38 +351,64 @@ pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, &TableLayout::from_env())?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, Column, TableLayout, MARKER};
+    use crate::{
+        day,
+        template::{
+            environment::Environment,
+            record::PartStatus,
+            timings::{BenchStats, HeapStats, PartTiming, Timing, Timings},
+        },
+        year,
+    };
+
+    fn millis(millis: u32) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos: f64::from(millis) * 1e+6,
+            samples: 10,
+            ..Default::default()
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
+                    year: year!(2024),
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    parse: None,
+                    part_1: millis(10),
+                    part_2: millis(20),
+                    part_1_status: None,
+                    part_2_status: None,
                 },
                 Timing {
+                    year: year!(2024),
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    parse: None,
+                    part_1: millis(30),
+                    part_2: millis(40),
+                    part_1_status: None,
+                    part_2_status: None,
                 },
                 Timing {
+                    year: year!(2024),
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    parse: None,
+                    part_1: millis(40),
+                    part_2: millis(50),
+                    part_1_status: None,
+                    part_2_status: None,
                 },
             ],
+            environment: None,
         }
     }
 
@@ -131,28 +416,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableLayout::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableLayout::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableLayout::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableLayout::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableLayout::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +445,20 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableLayout::default()).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "### 2024",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2024_01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024_02.rs) | `-` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024_04.rs) | `-` | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +467,131 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn groups_benchmarks_by_year() {
+        let mut timings = get_mock_timings();
+        timings.data[0].year = year!(2023);
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableLayout::default()).unwrap();
+
+        let pos_2023 = s.find("### 2023").unwrap();
+        let pos_2024 = s.find("### 2024").unwrap();
+        assert!(pos_2024 < pos_2023);
+        assert!(
+            s[pos_2023..].contains("| [Day 1](./src/bin/2023_01.rs) | `-` | `10.0ms` | `20.0ms` |")
+        );
+        assert!(!s[pos_2024..pos_2023].contains("[Day 1]"));
+    }
+
+    #[test]
+    fn shows_aborted_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].part_2_status = Some(PartStatus::Timeout);
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableLayout::default()).unwrap();
+        assert!(s.contains("| [Day 4](./src/bin/2024_04.rs) | `-` | `40.0ms` | `TIMEOUT` |"));
+    }
+
+    #[test]
+    fn shows_heap_usage() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().heap = Some(HeapStats {
+            peak_bytes: 512,
+            allocations: 2,
+            allocated_bytes: 768,
+        });
+        timings.data[0].part_2.as_mut().unwrap().heap = Some(HeapStats {
+            peak_bytes: 3 * 1024 * 1024,
+            allocations: 10,
+            allocated_bytes: 4 * 1024 * 1024,
+        });
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableLayout::default()).unwrap();
+        assert!(
+            s.contains("| Day | Parse | Part 1 | Part 2 | Peak heap | Allocations | Allocated |")
+        );
+        assert!(s.contains("| [Day 1](./src/bin/2024_01.rs) | `-` | `10.0ms` | `20.0ms` | `3.0 MiB` | `12` | `4.0 MiB` |"));
+        assert!(s.contains(
+            "| [Day 2](./src/bin/2024_02.rs) | `-` | `30.0ms` | `40.0ms` | `-` | `-` | `-` |"
+        ));
+    }
+
+    #[test]
+    fn prefers_median_timings() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().stats = Some(BenchStats {
+            samples: 10,
+            median: 9_500_000.0,
+            ..Default::default()
+        });
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableLayout::default()).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/2024_01.rs) | `-` | `9.5ms` | `20.0ms` |"));
+    }
+
+    #[test]
+    fn labels_the_environment() {
+        let mut timings = get_mock_timings();
+        timings.environment = Some(Environment {
+            host: "laptop".into(),
+            cpu: "Apple M2".into(),
+            cores: 8,
+            os: "macos-aarch64".into(),
+            rustc: "1.83.0".into(),
+            profile: "release".into(),
+        });
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableLayout::default()).unwrap();
+        assert!(s.contains(
+            "## Benchmarks\n\n_Measured on `laptop` (Apple M2, 8 cores, macos-aarch64) with rustc 1.83.0, release profile._\n\n### 2024"
+        ));
+    }
+
+    #[test]
+    fn shows_optional_columns() {
+        let layout = TableLayout {
+            columns: vec![Column::Samples, Column::Share],
+            chart: false,
+        };
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &layout).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Samples | Share |\n| :---: | :---: | :---: | :---: | :---: |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2024_01.rs) | `10.0ms` | `20.0ms` | `10 / 10` | `15.8%` |"
+        ));
+        assert!(s.contains(
+            "| [Day 4](./src/bin/2024_04.rs) | `40.0ms` | `50.0ms` | `10 / 10` | `47.4%` |"
+        ));
+        assert!("parse".parse::<Column>().is_ok());
+        assert!("total".parse::<Column>().is_err());
+    }
+
+    #[test]
+    fn draws_charts() {
+        let layout = TableLayout {
+            chart: true,
+            ..Default::default()
+        };
+        let mut timings = get_mock_timings();
+        timings.data[1].part_1 = None;
+        timings.data[1].part_2 = None;
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings.clone(), 120.0, &layout).unwrap();
+        let expected = [
+            "```text",
+            "Day  4 ██████████████████████████████ 90.0ms",
+            "Day  1 ██████████                     30.0ms",
+            "```",
+        ]
+        .join("\n");
+        assert!(s.contains(&expected));
+
+        // the table is replaced as a whole, including the chart.
+        let first = s.clone();
+        update_content(&mut s, timings, 120.0, &layout).unwrap();
+        assert_eq!(s, first);
+    }
 }
@@ -0,0 +1,252 @@
+/// Machine-readable output of solution binaries.
+/// When called with `--format json`, a solution prints one JSON record per part instead of the human-readable output.
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{BenchStats, HeapStats};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    Unsolved,
+    /// The part returned an error.
+    Error,
+    /// The part panicked.
+    Crashed,
+    /// The part exceeded its time limit and was aborted.
+    Timeout,
+    /// The part exceeded its memory limit and was aborted.
+    OutOfMemory,
+}
+
+impl PartStatus {
+    /// Whether the part failed without an answer, i.e. returned an error, panicked or exceeded a limit.
+    pub fn is_aborted(self) -> bool {
+        matches!(
+            self,
+            PartStatus::Error | PartStatus::Crashed | PartStatus::Timeout | PartStatus::OutOfMemory
+        )
+    }
+
+    /// Short, uppercase label of the status, e.g. `TIMEOUT`.
+    pub fn label(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "SOLVED",
+            PartStatus::Unsolved => "UNSOLVED",
+            PartStatus::Error => "ERROR",
+            PartStatus::Crashed => "CRASH",
+            PartStatus::Timeout => "TIMEOUT",
+            PartStatus::OutOfMemory => "OOM",
+        }
+    }
+}
+
+impl Display for PartStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Error => "error",
+            PartStatus::Crashed => "crashed",
+            PartStatus::Timeout => "timeout",
+            PartStatus::OutOfMemory => "oom",
+        })
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "unsolved" => Ok(PartStatus::Unsolved),
+            "error" => Ok(PartStatus::Error),
+            "crashed" => Ok(PartStatus::Crashed),
+            "timeout" => Ok(PartStatus::Timeout),
+            "oom" => Ok(PartStatus::OutOfMemory),
+            s => Err(format!("Unknown part status `{s}`.")),
+        }
+    }
+}
+
+/// The result of running one part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub part: u8,
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    /// Duration of a single run, averaged over all samples if benched.
+    pub duration_nanos: f64,
+    pub samples: u64,
+    pub stats: Option<BenchStats>,
+    /// Heap usage of a single run, when called with `--memory`.
+    pub heap: Option<HeapStats>,
+    pub error: Option<String>,
+}
+
+impl PartRecord {
+    /// Serializes the record to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("record should be serializable")
+    }
+
+    /// Parses a line of output. Returns `None` for lines that are not records.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+        let json = JsonValue::from_str(line).ok()?;
+        PartRecord::try_from(&json).ok()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: Option<&String>) -> JsonValue {
+    value.map_or(JsonValue::Null, |s| JsonValue::String(s.clone()))
+}
+
+impl From<&PartRecord> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert("answer".into(), optional_string(value.answer.as_ref()));
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "heap".into(),
+            value.heap.as_ref().map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert("error".into(), optional_string(value.error.as_ref()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected record.{key} to be a number."))
+        };
+
+        let optional_string = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected record.{key} to be null or string.")),
+        };
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        let heap = match json.get("heap") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(HeapStats::try_from(v)?),
+        };
+
+        Ok(PartRecord {
+            part: number("part")? as u8,
+            status,
+            answer: optional_string("answer")?,
+            duration_nanos: number("duration_nanos")?,
+            samples: number("samples")? as u64,
+            stats,
+            heap,
+            error: optional_string("error")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartRecord, PartStatus};
+    use crate::template::timings::{BenchStats, HeapStats};
+
+    #[test]
+    fn roundtrips_records() {
+        let record = PartRecord {
+            part: 2,
+            status: PartStatus::Solved,
+            answer: Some("#.#\n(1, 2) @ 3 samples)".into()),
+            duration_nanos: 1234.0,
+            samples: 100,
+            stats: Some(BenchStats {
+                samples: 100,
+                median: 1200.0,
+                ..Default::default()
+            }),
+            heap: Some(HeapStats {
+                peak_bytes: 1024,
+                allocations: 3,
+                allocated_bytes: 2048,
+            }),
+            error: None,
+        };
+
+        let line = record.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartRecord::from_json_line(&line), Some(record));
+    }
+
+    #[test]
+    fn handles_unsolved_records() {
+        let line = r#"{"part":1,"status":"unsolved","answer":null,"duration_nanos":10,"samples":1,"stats":null,"error":null}"#;
+        let record = PartRecord::from_json_line(line).unwrap();
+        assert_eq!(record.status, PartStatus::Unsolved);
+        assert_eq!(record.answer, None);
+    }
+
+    #[test]
+    fn handles_aborted_records() {
+        let line = r#"{"part":2,"status":"timeout","answer":null,"duration_nanos":0,"samples":0,"stats":null,"error":"TIMEOUT (exceeded the time limit of 1s)"}"#;
+        let record = PartRecord::from_json_line(line).unwrap();
+        assert_eq!(record.status, PartStatus::Timeout);
+        assert!(record.status.is_aborted());
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(PartRecord::from_json_line("Part 1: 42 (1.0ms)"), None);
+        assert_eq!(PartRecord::from_json_line("{ not json"), None);
+    }
+}
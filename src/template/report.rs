@@ -0,0 +1,554 @@
+/// Exports of the stored benchmarks, for use outside of the readme.
+use std::{fmt::Display, str::FromStr};
+
+use crate::template::{
+    history::{History, Run},
+    readme_benchmarks::{construct_tables, rank_days, Column, TableLayout},
+    runner::format_nanos,
+    timings::{Timing, Timings},
+};
+
+/// The formats of `cargo export`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Markdown,
+    Html,
+    Svg,
+}
+
+const TITLE: &str = "Advent of Code benchmarks";
+
+/// Width of the SVG chart, and of the labels and bars within it.
+const SVG_WIDTH: f64 = 720.0;
+const SVG_LABEL_WIDTH: f64 = 110.0;
+const SVG_BAR_WIDTH: f64 = 500.0;
+const SVG_ROW_HEIGHT: f64 = 22.0;
+
+impl ExportFormat {
+    /// The format of a file, by its extension, e.g. `benchmarks.csv`.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let (_, extension) = path.rsplit_once('.')?;
+        extension.parse().ok()
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = UnknownFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "html" => Ok(ExportFormat::Html),
+            "svg" => Ok(ExportFormat::Svg),
+            _ => Err(UnknownFormatError(s.to_string())),
+        }
+    }
+}
+
+/// The stored timings as CSV, with one row per part. Durations are in nanoseconds.
+pub fn timings_csv(timings: &Timings) -> String {
+    let mut lines = vec![CSV_HEADER.join(",")];
+    lines.extend(
+        timings
+            .data
+            .iter()
+            .flat_map(csv_rows)
+            .map(|row| row.join(",")),
+    );
+    lines.join("\n") + "\n"
+}
+
+/// Every run of the benchmark history as CSV, with one row per part and run.
+pub fn history_csv(history: &History) -> String {
+    let mut header = vec!["commit", "dirty", "timestamp", "host"];
+    header.extend(CSV_HEADER);
+
+    let mut lines = vec![header.join(",")];
+    for run in &history.runs {
+        let host = run
+            .timings
+            .environment
+            .as_ref()
+            .map(|environment| environment.host.as_str())
+            .unwrap_or_default();
+        let tags = [
+            run.commit.clone().unwrap_or_default(),
+            run.is_dirty.to_string(),
+            run.timestamp.to_string(),
+            csv_field(host),
+        ];
+        for row in run.timings.data.iter().flat_map(csv_rows) {
+            lines.push(
+                tags.iter()
+                    .chain(&row)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+const CSV_HEADER: [&str; 15] = [
+    "year",
+    "day",
+    "part",
+    "status",
+    "nanos",
+    "samples",
+    "median",
+    "min",
+    "max",
+    "stddev",
+    "p95",
+    "outliers",
+    "peak_bytes",
+    "allocations",
+    "allocated_bytes",
+];
+
+/// The rows of the parts of a day that ran. The parse phase is part `0`, as in the records of a solution.
+fn csv_rows(timing: &Timing) -> Vec<Vec<String>> {
+    let statuses = [None, timing.part_1_status, timing.part_2_status];
+
+    [0, 1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let phase = timing.part(part);
+            let status = match (phase, statuses[usize::from(part)]) {
+                (_, Some(status)) => status.to_string(),
+                (Some(_), None) => "solved".to_string(),
+                (None, None) => return None,
+            };
+
+            let number = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+            let integer = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+            let stats = phase.and_then(|p| p.stats.as_ref());
+            let heap = phase.and_then(|p| p.heap.as_ref());
+
+            Some(vec![
+                timing.year.to_string(),
+                timing.day.to_string(),
+                part.to_string(),
+                status,
+                number(phase.map(|p| p.nanos)),
+                integer(phase.map(|p| p.samples)),
+                number(stats.map(|s| s.median)),
+                number(stats.map(|s| s.min)),
+                number(stats.map(|s| s.max)),
+                number(stats.map(|s| s.stddev)),
+                number(stats.map(|s| s.p95)),
+                integer(stats.map(|s| s.outliers)),
+                integer(heap.map(|h| h.peak_bytes)),
+                integer(heap.map(|h| h.allocations)),
+                integer(heap.map(|h| h.allocated_bytes)),
+            ])
+        })
+        .collect()
+}
+
+/// Quotes a field that contains a separator or quote.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A standalone Markdown report with every column of the readme table, a chart per year and the benchmark history.
+pub fn markdown_report(timings: &Timings, history: &History) -> String {
+    let layout = TableLayout {
+        columns: vec![
+            Column::Parse,
+            Column::Samples,
+            Column::Share,
+            Column::Memory,
+        ],
+        chart: true,
+    };
+
+    let mut lines = vec![format!("# {TITLE}")];
+    lines.extend(construct_tables("#", timings, &layout));
+    lines.extend([
+        String::new(),
+        format!("**Total: {:.2}ms**", timings.total_millis()),
+    ]);
+
+    if !history.runs.is_empty() {
+        lines.extend([
+            String::new(),
+            "## History".to_string(),
+            String::new(),
+            "| Date | Commit | Host | Days | Total |".to_string(),
+            "| :---: | :---: | :---: | :---: | :---: |".to_string(),
+        ]);
+        for run in history.runs.iter().rev() {
+            let [date, commit, host, days, total] = history_cells(run);
+            lines.push(format!(
+                "| {date} | `{commit}` | `{host}` | {days} | `{total}` |"
+            ));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// A self-contained HTML page with a table per year, a chart of all days and the benchmark history.
+pub fn html_report(timings: &Timings, history: &History) -> String {
+    let layout = TableLayout {
+        columns: vec![
+            Column::Parse,
+            Column::Samples,
+            Column::Share,
+            Column::Memory,
+        ],
+        chart: false,
+    };
+
+    let mut body = vec![format!("<h1>{TITLE}</h1>")];
+
+    if let Some(environment) = &timings.environment {
+        body.push(format!(
+            "<p>Measured on {}.</p>",
+            escape(&environment.to_string().replace('`', ""))
+        ));
+    }
+
+    for year in timings.years().into_iter().rev() {
+        let days: Vec<&Timing> = timings.data.iter().filter(|t| t.year == year).collect();
+        let year_nanos: f64 = days.iter().map(|t| t.total_nanos()).sum();
+        let columns = layout.year_columns(&days);
+
+        body.push(format!("<h2>{year}</h2>"));
+        body.push("<table>".to_string());
+        body.push(html_row(
+            "th",
+            ["Day"]
+                .into_iter()
+                .chain(columns.iter().flat_map(|c| c.headers()))
+                .map(str::to_string),
+        ));
+        for timing in &days {
+            body.push(html_row(
+                "td",
+                [format!("Day {}", timing.day.into_inner())]
+                    .into_iter()
+                    .chain(columns.iter().flat_map(|c| c.cells(timing, year_nanos))),
+            ));
+        }
+        body.push("</table>".to_string());
+    }
+
+    body.push("<h2>Ranking</h2>".to_string());
+    body.push(svg_chart(timings));
+    body.push(format!(
+        "<p><strong>Total: {:.2}ms</strong></p>",
+        timings.total_millis()
+    ));
+
+    if !history.runs.is_empty() {
+        body.push("<h2>History</h2>".to_string());
+        body.push("<table>".to_string());
+        body.push(html_row(
+            "th",
+            ["Date", "Commit", "Host", "Days", "Total"].map(str::to_string),
+        ));
+        for run in history.runs.iter().rev() {
+            body.push(html_row("td", history_cells(run)));
+        }
+        body.push("</table>".to_string());
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{TITLE}</title>\n\
+        <style>{HTML_STYLE}</style>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        body.join("\n")
+    )
+}
+
+const HTML_STYLE: &str = "body { font-family: sans-serif; margin: 2em auto; max-width: 60em; } \
+    table { border-collapse: collapse; margin-bottom: 1em; } \
+    th, td { border: 1px solid #ccc; padding: 0.25em 0.75em; text-align: center; } \
+    td { font-family: monospace; }";
+
+fn html_row(tag: &str, cells: impl IntoIterator<Item = String>) -> String {
+    let cells: String = cells
+        .into_iter()
+        .map(|cell| format!("<{tag}>{}</{tag}>", escape(&cell)))
+        .collect();
+    format!("<tr>{cells}</tr>")
+}
+
+/// The date, commit, host, number of days and total runtime of a run.
+fn history_cells(run: &Run) -> [String; 5] {
+    let host = run
+        .timings
+        .environment
+        .as_ref()
+        .map_or("unknown", |environment| environment.host.as_str());
+    [
+        format_timestamp(run.timestamp),
+        run.label(),
+        host.to_string(),
+        run.timings.data.len().to_string(),
+        format_nanos(run.timings.data.iter().map(Timing::total_nanos).sum()),
+    ]
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A bar chart of all days with a timing, slowest first.
+pub fn svg_chart(timings: &Timings) -> String {
+    let days: Vec<&Timing> = timings.data.iter().collect();
+    let ranked = rank_days(&days);
+    let max = ranked.first().map_or(0.0, |(_, nanos)| *nanos);
+    // the year is only shown if the chart has days of several years.
+    let has_years = timings.years().len() > 1;
+
+    #[allow(clippy::cast_precision_loss)]
+    let height = (ranked.len().max(1) as f64) * SVG_ROW_HEIGHT;
+
+    let mut lines = vec![format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{SVG_WIDTH}\" height=\"{height}\" \
+        viewBox=\"0 0 {SVG_WIDTH} {height}\" font-family=\"monospace\" font-size=\"12\">"
+    )];
+
+    if ranked.is_empty() {
+        lines.push("<text x=\"0\" y=\"15\">No benchmarks stored.</text>".to_string());
+    }
+
+    for (index, (timing, nanos)) in ranked.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = index as f64 * SVG_ROW_HEIGHT;
+        let width = nanos / max * SVG_BAR_WIDTH;
+        let label = if has_years {
+            format!("{} Day {}", timing.year, timing.day.into_inner())
+        } else {
+            format!("Day {}", timing.day.into_inner())
+        };
+
+        lines.push(format!(
+            "<text x=\"0\" y=\"{:.1}\">{label}</text>",
+            y + 15.0
+        ));
+        lines.push(format!(
+            "<rect x=\"{SVG_LABEL_WIDTH}\" y=\"{:.1}\" width=\"{width:.1}\" height=\"16\" fill=\"#2e7d32\"/>",
+            y + 3.0
+        ));
+        lines.push(format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+            SVG_LABEL_WIDTH + width + 6.0,
+            y + 15.0,
+            format_nanos(*nanos)
+        ));
+    }
+
+    lines.push("</svg>".to_string());
+    lines.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Formats seconds since the unix epoch as a UTC date and time, e.g. `2024-12-16 10:23 UTC`.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+
+    // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02} UTC",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/// An error which can be returned when parsing an [`ExportFormat`].
+#[derive(Debug)]
+pub struct UnknownFormatError(String);
+
+impl std::error::Error for UnknownFormatError {}
+
+impl Display for UnknownFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown format `{}`, expecting one of csv, md, html and svg",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        format_timestamp, history_csv, html_report, markdown_report, svg_chart, timings_csv,
+        ExportFormat,
+    };
+    use crate::{
+        day,
+        template::{
+            history::{History, Run},
+            record::PartStatus,
+            timings::{HeapStats, PartTiming, Timing, Timings},
+        },
+        year,
+    };
+
+    fn part(nanos: f64) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos,
+            samples: 10,
+            ..Default::default()
+        })
+    }
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    year: year!(2024),
+                    day: day!(1),
+                    parse: part(1e+6),
+                    part_1: part(1e+7),
+                    part_2: part(2e+7),
+                    part_1_status: None,
+                    part_2_status: None,
+                },
+                Timing {
+                    year: year!(2024),
+                    day: day!(2),
+                    parse: None,
+                    part_1: part(4e+7),
+                    part_2: None,
+                    part_1_status: None,
+                    part_2_status: Some(PartStatus::Timeout),
+                },
+            ],
+            environment: None,
+        }
+    }
+
+    fn get_mock_history() -> History {
+        History {
+            runs: vec![Run {
+                commit: Some("1a2b3c4d5e".into()),
+                is_dirty: false,
+                timestamp: 1_734_344_580,
+                timings: get_mock_timings(),
+            }],
+        }
+    }
+
+    #[test]
+    fn chooses_formats() {
+        assert_eq!(
+            "md".parse::<ExportFormat>().unwrap(),
+            ExportFormat::Markdown
+        );
+        assert!("pdf".parse::<ExportFormat>().is_err());
+        assert_eq!(
+            ExportFormat::from_path("out/benchmarks.svg"),
+            Some(ExportFormat::Svg)
+        );
+        assert_eq!(ExportFormat::from_path("benchmarks"), None);
+    }
+
+    #[test]
+    fn exports_csv() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().heap = Some(HeapStats {
+            peak_bytes: 512,
+            allocations: 2,
+            allocated_bytes: 768,
+        });
+        let csv = timings_csv(&timings);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("year,day,part,status,nanos,samples,median"));
+        assert_eq!(lines[1], "2024,01,0,solved,1000000,10,,,,,,,,,");
+        assert_eq!(lines[2], "2024,01,1,solved,10000000,10,,,,,,,512,2,768");
+        assert_eq!(lines[5], "2024,02,2,timeout,,,,,,,,,,,");
+    }
+
+    #[test]
+    fn exports_history_csv() {
+        let csv = history_csv(&get_mock_history());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines[0].starts_with("commit,dirty,timestamp,host,year,day,part"));
+        assert_eq!(
+            lines[1],
+            "1a2b3c4d5e,false,1734344580,,2024,01,0,solved,1000000,10,,,,,,,,,"
+        );
+    }
+
+    #[test]
+    fn exports_markdown() {
+        let report = markdown_report(&get_mock_timings(), &get_mock_history());
+        assert!(report.starts_with("# Advent of Code benchmarks\n"));
+        assert!(report.contains("## 2024\n"));
+        assert!(report.contains(
+            "| [Day 1](./src/bin/2024_01.rs) | `1.0ms` | `10.0ms` | `20.0ms` | `10 / 10` | `43.7%` |"
+        ));
+        assert!(report.contains("```text\nDay  2 "));
+        assert!(report.contains("**Total: 71.00ms**"));
+        assert!(report.contains("| 2024-12-16 10:23 UTC | `1a2b3c4` | `unknown` | 2 | `71.0ms` |"));
+    }
+
+    #[test]
+    fn exports_html() {
+        let report = html_report(&get_mock_timings(), &History::default());
+        assert!(report.starts_with("<!DOCTYPE html>"));
+        assert!(report.contains("<tr><th>Day</th><th>Parse</th><th>Part 1</th><th>Part 2</th>"));
+        assert!(report.contains("<tr><td>Day 2</td><td>-</td><td>40.0ms</td><td>TIMEOUT</td>"));
+        assert!(report.contains("<svg"));
+        assert!(!report.contains("<h2>History</h2>"));
+    }
+
+    #[test]
+    fn exports_svg() {
+        let svg = svg_chart(&get_mock_timings());
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"720\" height=\"44\""));
+        // slowest first, and the slowest day spans the full width.
+        let day_2 = svg.find(">Day 2<").unwrap();
+        let day_1 = svg.find(">Day 1<").unwrap();
+        assert!(day_2 < day_1);
+        assert!(svg.contains("width=\"500.0\""));
+
+        let empty = svg_chart(&Timings::default());
+        assert!(empty.contains("No benchmarks stored."));
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(1_734_344_580), "2024-12-16 10:23 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00 UTC");
+    }
+}
@@ -1,21 +1,75 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    environment::Environment,
+    runner::{RunOptions, Solution},
+    Day, InputSource, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
+    record::{PartRecord, PartStatus},
+    runner::part_label,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Answers reported by a solution, indexed by part.
+pub type PartAnswers = [Option<String>; 2];
+
+/// Results of running a set of days.
+pub struct MultiRun {
+    /// Benchmark timings, if the run was timed.
+    pub timings: Option<Timings>,
+    /// Answers of every day that has been scaffolded.
+    pub answers: Vec<(PuzzleId, PartAnswers)>,
+    /// Parts that were aborted, i.e. panicked or exceeded a limit.
+    pub aborted: Vec<AbortedPart>,
+}
+
+/// A part that panicked or exceeded a limit. An aborted parse phase is reported as part `0`.
+pub struct AbortedPart {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub status: PartStatus,
+    pub error: String,
+}
+
+/// The parts of a puzzle that were aborted in a run.
+pub fn aborted_parts(puzzle: PuzzleId, records: &[PartRecord]) -> Vec<AbortedPart> {
+    records
+        .iter()
+        .filter(|r| r.status.is_aborted())
+        .map(|r| AbortedPart {
+            puzzle,
+            part: r.part,
+            status: r.status,
+            error: r.error.clone().unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Run a set of days of a year. If a solution registry is passed, no limits are set and the heap usage is not profiled,
+/// the days are run in-process. Otherwise, each day is run in its own binary.
+/// Timings are collected if `options.is_timed` is set, including the heap usage if `options.is_profiled` is set.
+/// They are tagged with the environment they were measured in.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    options: RunOptions,
+    solutions: Option<&[Solution]>,
+) -> MultiRun {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut answers: Vec<(PuzzleId, PartAnswers)> = Vec::with_capacity(days_to_run.len());
+    let mut aborted: Vec<AbortedPart> = vec![];
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    all_days(year)
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
+            let puzzle = PuzzleId::new(year, day);
+
             if need_space {
                 println!();
             }
@@ -24,18 +78,41 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let records = run_day(puzzle, &InputSource::Puzzle, options, solutions);
 
-            if output.is_empty() {
+            if records.is_empty() {
                 println!("Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                let val = child_commands::parse_exec_time(&records, puzzle);
                 timings.push(val);
+                answers.push((puzzle, child_commands::parse_answers(&records)));
+                aborted.extend(aborted_parts(puzzle, &records));
             }
         });
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    if !aborted.is_empty() {
+        println!("\n{ANSI_BOLD}Aborted:{ANSI_RESET}");
+        for abort in &aborted {
+            println!(
+                "Day {} {}: {}",
+                abort.puzzle.day,
+                part_label(abort.part),
+                abort.error
+            );
+        }
+    }
+
+    let timings = if options.is_timed {
+        // in-process, the solutions are built with the profile of this binary.
+        let is_release = match solutions {
+            Some(_) if is_in_process(options) => !cfg!(debug_assertions),
+            _ => options.is_release,
+        };
+        let profile = if is_release { "release" } else { "dev" };
+        let timings = Timings {
+            data: timings,
+            environment: Some(Environment::detect(profile)),
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -43,9 +120,58 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun {
+        timings,
+        answers,
+        aborted,
+    }
+}
+
+/// Run all parts of a puzzle against an input and collect their records. Returns no records if the day is not scaffolded.
+pub fn run_day(
+    puzzle: PuzzleId,
+    source: &InputSource,
+    options: RunOptions,
+    solutions: Option<&[Solution]>,
+) -> Vec<PartRecord> {
+    match solutions {
+        Some(solutions) if is_in_process(options) => {
+            run_in_process(solutions, puzzle, source, options)
+        }
+        _ => child_commands::run_solution(puzzle, source, options).unwrap(),
     }
 }
 
+/// Whether days run in-process with these options. Parts with limits run in processes of their own,
+/// which are started by the solution binaries, and only the solution binaries count the heap usage.
+fn is_in_process(options: RunOptions) -> bool {
+    options.limits.is_unlimited() && !options.is_profiled
+}
+
+/// Run all parts of a puzzle from the solution registry.
+fn run_in_process(
+    solutions: &[Solution],
+    puzzle: PuzzleId,
+    source: &InputSource,
+    options: RunOptions,
+) -> Vec<PartRecord> {
+    let Some(solution) = solutions.iter().find(|s| s.puzzle == puzzle) else {
+        return vec![];
+    };
+
+    let input = match source.read(puzzle) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("could not open input file: {e}");
+            return vec![];
+        }
+    };
+
+    (solution.run)(&input, options)
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,16 +185,16 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading the records they emit.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, PartAnswers};
+    use crate::template::{
+        record::{PartRecord, PartStatus},
+        runner::{print_record, RunOptions, PARSE_PART},
+        timings::PartTiming,
+        InputSource, PuzzleId,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,28 +202,37 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given puzzle against an input and collect the records of its parts.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        source: &InputSource,
+        options: RunOptions,
+    ) -> Result<Vec<PartRecord>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&puzzle.bin_path()).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = puzzle.bin_name();
+        let mut option_args = options.to_args();
+        option_args.extend(source.to_args());
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
-        if is_release {
+        if options.is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if options.counts_heap() {
+            args.extend(["--features", "count-alloc"]);
         }
 
+        args.extend(["--", "--format", "json"]);
+
+        // mirror flags such as `--time` to child invocations.
+        args.extend(option_args.iter().map(String::as_str));
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing records from stdout.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -108,7 +243,7 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut records = vec![];
 
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
@@ -118,75 +253,77 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            match PartRecord::from_json_line(&line) {
+                Some(record) => {
+                    print_record(&record);
+                    records.push(record);
+                }
+                None => println!("{line}"),
+            }
         }
 
         thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(records)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    pub fn parse_exec_time(records: &[PartRecord], puzzle: PuzzleId) -> super::Timing {
         let mut timings = super::Timing {
-            day,
+            year: puzzle.year,
+            day: puzzle.day,
+            parse: None,
             part_1: None,
             part_2: None,
-            total_nanos: 0_f64,
+            part_1_status: None,
+            part_2_status: None,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+        // parts without an answer keep their status, so the timings they replace are cleared when merged.
+        // an aborted parse phase aborts both parts.
+        for record in records.iter().filter(|r| r.status != PartStatus::Solved) {
+            match record.part {
+                PARSE_PART => {
+                    timings.part_1_status = Some(record.status);
+                    timings.part_2_status = Some(record.status);
                 }
+                1 => timings.part_1_status = Some(record.status),
+                2 => timings.part_2_status = Some(record.status),
+                _ => {}
+            }
+        }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+        records
+            .iter()
+            .filter(|r| r.status == PartStatus::Solved)
+            .for_each(|record| {
+                let timing = Some(PartTiming {
+                    nanos: record.duration_nanos,
+                    samples: record.samples,
+                    stats: record.stats.clone(),
+                    heap: record.heap.clone(),
+                });
+                match record.part {
+                    PARSE_PART => timings.parse = timing,
+                    1 => timings.part_1 = timing,
+                    2 => timings.part_2 = timing,
+                    _ => {}
                 }
-
-                timings.total_nanos += nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    pub fn parse_answers(records: &[PartRecord]) -> PartAnswers {
+        let mut answers: PartAnswers = [None, None];
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        for record in records {
+            if let Some(answer) = answers.get_mut(usize::from(record.part).wrapping_sub(1)) {
+                answer.clone_from(&record.answer);
+            }
+        }
+
+        answers
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,53 +342,156 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time};
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{
+                record::{PartRecord, PartStatus},
+                timings::{BenchStats, HeapStats},
+                PuzzleId,
+            },
+            year,
+        };
+
+        const PUZZLE: PuzzleId = PuzzleId::new(year!(2024), day!(1));
+
+        fn record(part: u8, answer: Option<&str>, duration_nanos: f64) -> PartRecord {
+            PartRecord {
+                part,
+                status: if answer.is_some() {
+                    PartStatus::Solved
+                } else {
+                    PartStatus::Unsolved
+                },
+                answer: answer.map(ToString::to_string),
+                duration_nanos,
+                samples: 1,
+                stats: None,
+                heap: None,
+                error: None,
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    record(1, Some("0"), 74.13),
+                    record(2, Some("10"), 74_130_000.0),
                 ],
-                day!(1),
+                PUZZLE,
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos(), 74130074.13_f64);
+            assert_approx_eq!(res.part_1.unwrap().nanos, 74.13_f64);
+            assert_approx_eq!(res.part_2.unwrap().nanos, 74_130_000_f64);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
+        fn parses_sample_stats() {
+            let mut part_1 = record(1, Some("0"), 1_000_000.0);
+            part_1.samples = 90;
+            part_1.stats = Some(BenchStats {
+                samples: 90,
+                outliers: 10,
+                median: 900_000.0,
+                ..Default::default()
+            });
+
+            let res = parse_exec_time(&[part_1, record(2, Some("10"), 10.0)], PUZZLE);
+            let part_1 = res.part_1.unwrap();
+            assert_eq!(part_1.samples, 90);
+            let stats = part_1.stats.unwrap();
+            assert_eq!(stats.samples, 90);
+            assert_eq!(stats.outliers, 10);
+            assert_approx_eq!(stats.median, 900_000_f64);
+            assert_eq!(res.part_2.unwrap().stats.is_none(), true);
+        }
+
+        #[test]
+        fn parses_heap_stats() {
+            let mut part_2 = record(2, Some("10"), 10.0);
+            part_2.heap = Some(HeapStats {
+                peak_bytes: 2048,
+                allocations: 4,
+                allocated_bytes: 4096,
+            });
+
+            let res = parse_exec_time(&[record(1, Some("0"), 10.0), part_2], PUZZLE);
+            assert_eq!(res.part_1.unwrap().heap, None);
+            assert_eq!(res.part_2.unwrap().heap.unwrap().allocations, 4);
+        }
+
+        #[test]
+        fn parses_parse_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    PartRecord {
+                        status: PartStatus::Solved,
+                        ..record(0, None, 2_000.0)
+                    },
+                    record(1, Some("0"), 1_000.0),
+                    record(2, Some("10"), 3_000.0),
                 ],
-                day!(1),
+                PUZZLE,
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.parse.as_ref().unwrap().nanos, 2_000_f64);
+            assert_approx_eq!(res.total_nanos(), 6_000_f64);
         }
 
         #[test]
-        fn parses_missing_parts() {
+        fn parses_aborted_parts() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    record(1, Some("0"), 1_000.0),
+                    PartRecord {
+                        status: PartStatus::Timeout,
+                        ..record(2, None, 0.0)
+                    },
                 ],
-                day!(1),
+                PUZZLE,
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_approx_eq!(res.part_1.unwrap().nanos, 1_000_f64);
+            assert_eq!(res.part_1_status, None);
+            assert_eq!(res.part_2, None);
+            assert_eq!(res.part_2_status, Some(PartStatus::Timeout));
+
+            let res = parse_exec_time(
+                &[PartRecord {
+                    status: PartStatus::OutOfMemory,
+                    ..record(0, None, 0.0)
+                }],
+                PUZZLE,
+            );
+            assert_eq!(res.part_1_status, Some(PartStatus::OutOfMemory));
+            assert_eq!(res.part_2_status, Some(PartStatus::OutOfMemory));
+        }
+
+        #[test]
+        fn parses_missing_parts() {
+            let res = parse_exec_time(&[record(1, None, 10.0), record(2, None, 10.0)], PUZZLE);
+            assert_approx_eq!(res.total_nanos(), 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_1_status, Some(PartStatus::Unsolved));
+            assert_eq!(res.part_2_status, Some(PartStatus::Unsolved));
+        }
+
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(&[
+                record(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 10.0),
+                record(2, Some("#..\n.##"), 10.0),
+            ]);
+            assert_eq!(res[0].as_deref(), Some("@ @ @ ( ) ms (2s @ 5 samples)"));
+            assert_eq!(res[1].as_deref(), Some("#..\n.##"));
+        }
+
+        #[test]
+        fn parses_missing_answers() {
+            let res = parse_answers(&[record(1, None, 10.0)]);
+            assert_eq!(res[0], None);
+            assert_eq!(res[1], None);
         }
     }
 }
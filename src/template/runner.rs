@@ -1,66 +1,717 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stdout, BufRead, BufReader, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Once;
+use std::thread;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{
+    cell::{Cell, RefCell},
+    cmp, env, process,
+};
 
+use crate::template::answers::{self, Answers};
+use crate::template::limits::format_mib;
+use crate::template::record::{PartRecord, PartStatus};
+use crate::template::timings::{BenchStats, HeapStats};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{alloc, aoc_client, InputSource, Limits, PuzzleId, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// The entry of a day in the solution registry, created by the `solution!` macro.
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs the parse phase, if any, and all parts against an input.
+    pub run: RunFn,
+}
+
+/// Runs a solution against an input with the given options. Returns one record per phase that ran.
+pub type RunFn = fn(&str, RunOptions) -> Vec<PartRecord>;
+
+/// How the parts of a solution are run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    /// Whether solution binaries are built in release mode. Runs in-process use the build of the main binary.
+    pub is_release: bool,
+    /// Whether parts are benched.
+    pub is_timed: bool,
+    /// Whether the heap usage of parts is profiled.
+    pub is_profiled: bool,
+    /// The only part that runs, if set. The parse phase always runs.
+    pub part: Option<u8>,
+    pub limits: Limits,
+}
+
+impl RunOptions {
+    /// Read the options of a solution binary from its arguments, e.g. `--time` and `--part 2`.
+    pub fn from_args(args: &[String]) -> Self {
+        RunOptions {
+            is_release: false,
+            is_timed: args.iter().any(|x| x == "--time"),
+            is_profiled: args.iter().any(|x| x == "--memory"),
+            part: args
+                .windows(2)
+                .find(|w| w[0] == "--part")
+                .and_then(|w| w[1].parse().ok()),
+            limits: Limits::from_args(args),
+        }
+    }
+
+    /// Arguments that pass these options on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.is_timed {
+            args.push("--time".into());
+        }
+        if self.is_profiled {
+            args.push("--memory".into());
+        }
+        if let Some(part) = self.part {
+            args.extend(["--part".into(), part.to_string()]);
+        }
+        args.extend(self.limits.to_args());
+        args
+    }
+
+    /// Whether heap usage is counted with these options, which needs a solution binary with the `count-alloc` feature.
+    pub fn counts_heap(&self) -> bool {
+        self.is_profiled || self.limits.memory.is_some()
+    }
+
+    /// Whether `part` runs with these options.
+    pub fn is_selected(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+}
+
+/// The part number of records of the parse phase.
+pub const PARSE_PART: u8 = 0;
+
+/// Marks a part process: a solution binary that was started by itself to run a single phase with limits.
+const PART_PROCESS_VAR: &str = "AOC_PART_PROCESS";
+
+/// Read the input of a solution binary from the source selected by its arguments.
+pub fn read_input(puzzle: PuzzleId) -> String {
+    let args: Vec<String> = env::args().collect();
+    let source = InputSource::from_args(&args);
+
+    match source.read(puzzle) {
+        Ok(input) => input,
+        Err(e) => {
+            match source.path(puzzle) {
+                Some(path) => eprintln!("could not open input file `{path}`: {e}"),
+                None => eprintln!("could not read input from {source}: {e}"),
+            }
+            process::exit(1);
+        }
+    }
+}
+
+/// The return type of a solution part: `Option<T>`, where `None` means that the part is not solved yet,
+/// or `Result<T, E>`, where an error is reported with its message.
+pub trait PartResult {
+    type Answer: Display;
+
+    /// Converts the result to an optional answer, or an error message.
+    fn into_answer(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        self.map(Some).map_err(|e| e.to_string())
+    }
+}
+
+/// Checks the result of a part against the expected answer of an example, which is `None` for unsolved parts.
+/// Used by the tests generated from example manifests.
+pub fn assert_example<R: PartResult>(result: R, expected: Option<&str>) {
+    let answer = result
+        .into_answer()
+        .map(|answer| answer.map(|a| a.to_string()));
+    assert_eq!(answer, Ok(expected.map(str::to_string)));
+}
+
+pub fn run_part<I: Clone + AsRef<str>, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    let options = options_from_args();
+    if !options.is_selected(part) {
+        return;
+    }
+
+    let input_id = answers::fingerprint(input.as_ref());
+    let is_json = is_json_output();
+
+    let record = execute_part(func, input, part, options, is_json);
+    report_part(record, puzzle, &input_id, is_json);
+}
+
+/// Run a solution part against the output of the parse phase.
+/// The raw input is used to identify the input when submitting.
+pub fn run_parsed_part<P, R: PartResult>(
+    func: impl Fn(&P) -> R,
+    parsed: &P,
+    input: &str,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    let options = options_from_args();
+    if !options.is_selected(part) {
+        return;
+    }
+
+    let input_id = answers::fingerprint(input);
+    let is_json = is_json_output();
+
+    let record = execute_part(func, parsed, part, options, is_json);
+    report_part(record, puzzle, &input_id, is_json);
+}
+
+/// Run the parse phase of a solution, printing its duration.
+/// Returns `None` if parsing panicked or exceeded its limits.
+pub fn run_parse<P>(func: impl Fn(&str) -> P, input: &str) -> Option<P> {
+    let is_json = is_json_output();
+    let mut options = options_from_args();
+
+    // the parse phase is timed in a part process of its own.
+    if is_part_process() && options.part != Some(PARSE_PART) {
+        options.is_timed = false;
+        options.is_profiled = false;
+    }
+
+    let (parsed, record) = execute_parse(func, input, options, is_json);
+
+    if is_json {
+        println!("{}", record.to_json_line());
+    }
+
+    parsed
+}
+
+/// Run the parse phase of a solution in-process, printing its duration. Used by the solution registry.
+pub fn run_parse_record<P>(
+    func: impl Fn(&str) -> P,
+    input: &str,
+    options: RunOptions,
+) -> (Option<P>, PartRecord) {
+    execute_parse(func, input, options, false)
+}
+
+fn report_part(record: PartRecord, puzzle: PuzzleId, input_id: &str, is_json: bool) {
+    if is_json {
+        println!("{}", record.to_json_line());
+    }
+
+    let part = record.part;
+
+    // the process that started a part process submits its result.
+    if is_part_process() {
+        return;
+    }
+
+    if let Some(result) = record.answer {
+        match submit_result(result, puzzle, part, input_id) {
+            Some(Ok(submission)) => {
+                println!("{}", submission.message);
+            }
+            Some(Err(e)) => {
+                eprintln!("Failed to submit result: {e}");
+                process::exit(1);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Run a solution part in-process, printing its result. Used by the solution registry.
+/// Returns `None` if the part is not selected by the options.
+pub fn run_part_record<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    part: u8,
+    options: RunOptions,
+) -> Option<PartRecord> {
+    options
+        .is_selected(part)
+        .then(|| execute_part(func, input, part, options, false))
+}
+
+fn execute_parse<P>(
+    func: impl Fn(&str) -> P,
+    input: &str,
+    options: RunOptions,
+    is_quiet: bool,
+) -> (Option<P>, PartRecord) {
+    let outcome = run_limited(
+        move || {
+            run_timed(
+                func,
+                input,
+                |_| {
+                    if !is_quiet {
+                        print!("Parse:");
+                    }
+                },
+                options.is_timed,
+                options.is_profiled,
+                is_quiet,
+            )
+        },
+        options.limits,
+    );
+
+    let (parsed, duration, stats, heap) = match outcome {
+        Ok(outcome) => outcome,
+        Err(abort) => {
+            let record = abort_record(PARSE_PART, &abort);
+            if !is_quiet {
+                print_abort(&record);
+            }
+            return (None, record);
+        }
+    };
+
+    if !is_quiet {
+        let duration_str = format_duration(&duration, stats.as_ref()) + &format_heap(heap.as_ref());
+        print_parse_result(&duration_str);
+    }
+
+    let record = PartRecord {
+        part: PARSE_PART,
+        status: PartStatus::Solved,
+        answer: None,
+        #[allow(clippy::cast_precision_loss)]
+        duration_nanos: duration.as_nanos() as f64,
+        samples: stats.as_ref().map_or(1, |s| s.samples),
+        stats,
+        heap,
+        error: None,
+    };
+
+    (Some(parsed), record)
+}
+
+fn execute_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    part: u8,
+    options: RunOptions,
+    is_quiet: bool,
+) -> PartRecord {
+    let part_str = part_label(part);
+
+    let outcome = {
+        let part_str = part_str.clone();
+        run_limited(
+            move || {
+                run_timed(
+                    move |input| func(input).into_answer(),
+                    input,
+                    |result| {
+                        if !is_quiet {
+                            let answer = result.as_ref().ok().and_then(Option::as_ref);
+                            print_result(&answer, &part_str, "");
+                        }
+                    },
+                    options.is_timed,
+                    options.is_profiled,
+                    is_quiet,
+                )
+            },
+            options.limits,
+        )
+    };
+
+    let (result, duration, stats, heap) = match outcome {
+        Ok(outcome) => outcome,
+        Err(abort) => {
+            let record = abort_record(part, &abort);
+            if !is_quiet {
+                print_abort(&record);
+            }
+            return record;
+        }
+    };
+
+    let (status, answer, error) = match result {
+        Ok(Some(answer)) => (PartStatus::Solved, Some(answer.to_string()), None),
+        Ok(None) => (PartStatus::Unsolved, None, None),
+        Err(error) => (PartStatus::Error, None, Some(error)),
+    };
+
+    let record = PartRecord {
+        part,
+        status,
+        answer,
+        #[allow(clippy::cast_precision_loss)]
+        duration_nanos: duration.as_nanos() as f64,
+        samples: stats.as_ref().map_or(1, |s| s.samples),
+        stats,
+        heap,
+        error,
+    };
+
+    if !is_quiet {
+        if record.status == PartStatus::Error {
+            print_abort(&record);
+        } else {
+            let duration_str = format_duration(&duration, record.stats.as_ref())
+                + &format_heap(record.heap.as_ref());
+            print_result(&record.answer, &part_str, &duration_str);
+        }
+    }
+
+    record
+}
+
+/// The reason a part was aborted.
+enum Abort {
+    /// The part panicked, with the panic message.
+    Panic(String),
+    Timeout(Duration),
+    /// The part exceeded the memory limit, in bytes.
+    OutOfMemory(u64),
+}
+
+fn abort_record(part: u8, abort: &Abort) -> PartRecord {
+    let (status, error) = match abort {
+        Abort::Panic(error) => (PartStatus::Crashed, error.clone()),
+        Abort::Timeout(timeout) => (
+            PartStatus::Timeout,
+            format!("TIMEOUT (exceeded the time limit of {timeout:?})"),
+        ),
+        Abort::OutOfMemory(memory) => (
+            PartStatus::OutOfMemory,
+            format!("OOM (exceeded the memory limit of {})", format_mib(*memory)),
+        ),
+    };
+
+    PartRecord {
+        part,
+        status,
+        answer: None,
+        duration_nanos: 0.0,
+        samples: 0,
+        stats: None,
+        heap: None,
+        error: Some(error),
+    }
+}
+
+/// Read the options of a solution binary from the environment and its arguments.
+fn options_from_args() -> RunOptions {
+    let args: Vec<String> = env::args().collect();
+    RunOptions::from_args(&args)
+}
+
+/// Whether this process is a part process, see [`run_isolated`].
+fn is_part_process() -> bool {
+    env::var_os(PART_PROCESS_VAR).is_some()
+}
+
+/// Whether the parts of a solution binary run in part processes, i.e. if limits are set.
+pub fn is_isolated() -> bool {
+    !is_part_process() && !options_from_args().limits.is_unlimited()
+}
+
+/// Runs the parse phase, if any, and the parts of a solution binary, each in a part process of its own.
+/// A part process runs this binary again for a single phase. It is killed when it exceeds the time limit,
+/// and aborts when it exceeds the memory limit. An aborted parse phase skips the parts.
+/// The input is read once, e.g. from stdin, and passed to every part process on its stdin.
+pub fn run_isolated(puzzle: PuzzleId, has_parse: bool, parts: &[u8]) {
+    let options = options_from_args();
+    let is_json = is_json_output();
+    let input = read_input(puzzle);
+    let input_id = answers::fingerprint(&input);
+
+    if options.limits.memory.is_some() && !alloc::IS_COUNTING {
+        eprintln!(
+            "The memory limit is not enforced: build the solution with the `count-alloc` feature."
+        );
+    }
+
+    if has_parse {
+        let command = part_process_command(PARSE_PART);
+        let record = run_part_process(command, &input, PARSE_PART, options.limits);
+        if is_json {
+            println!("{}", record.to_json_line());
+        } else {
+            print_record(&record);
+        }
+        if record.status.is_aborted() {
+            return;
+        }
+    }
+
+    for &part in parts.iter().filter(|part| options.is_selected(**part)) {
+        let record = run_part_process(part_process_command(part), &input, part, options.limits);
+        if !is_json {
+            print_record(&record);
+        }
+        report_part(record, puzzle, &input_id, is_json);
+    }
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// The command that runs a single phase of this binary in a part process.
+/// It keeps the arguments of this process, e.g. the limits, but reads its input from stdin.
+fn part_process_command(part: u8) -> Command {
+    let mut command = Command::new(env::current_exe().expect("could not find the solution binary"));
+    let mut args = env::args().skip(1);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // the part, format and input source are replaced, and only this process submits.
+            "--part" | "--format" | "--submit" | "--input" | "--example-part"
+            | "--example-file" => {
+                args.next();
+            }
+            "--stdin" | "--example" => {}
+            _ => {
+                command.arg(arg);
+            }
+        }
+    }
+
+    command
+        .args(["--part", &part.to_string(), "--format", "json", "--stdin"])
+        .env(PART_PROCESS_VAR, "1");
+    command
+}
+
+/// Runs a part process with the input on its stdin, and returns the record of its part.
+/// The process is killed if it exceeds the time limit. Lines of its output that are not records are printed.
+fn run_part_process(mut command: Command, input: &str, part: u8, limits: Limits) -> PartRecord {
+    let spawned = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            let abort = Abort::Panic(format!("could not start part process: {e}"));
+            return abort_record(part, &abort);
+        }
+    };
+
+    // written on a separate thread, so a process that prints before reading all of its input does not block.
+    let mut stdin = child.stdin.take().expect("stdin should be piped");
+    let input = input.to_string();
+    thread::spawn(move || {
+        // the process may exit without reading its input.
+        let _ = stdin.write_all(input.as_bytes());
+    });
+
+    // stderr is forwarded up to the message of a failed allocation, which is reported as out of memory instead.
+    let stderr = BufReader::new(child.stderr.take().expect("stderr should be piped"));
+    let stderr_thread = thread::spawn(move || {
+        let mut is_out_of_memory = false;
+        for line in stderr.lines().map_while(Result::ok) {
+            is_out_of_memory |= line.starts_with(alloc::ALLOC_ERROR_MESSAGE);
+            if !is_out_of_memory {
+                eprintln!("{line}");
+            }
+        }
+        is_out_of_memory
+    });
+
+    // the output is read on a separate thread, so the process can be killed while it does not print anything.
+    let stdout = BufReader::new(child.stdout.take().expect("stdout should be piped"));
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in stdout.lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut records: Vec<PartRecord> = vec![];
+    let mut timer = Instant::now();
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    loop {
+        let line = match limits.timeout {
+            Some(timeout) => receiver.recv_timeout(timeout.saturating_sub(timer.elapsed())),
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match line {
+            Ok(line) => match PartRecord::from_json_line(&line) {
+                Some(record) => {
+                    // the parse phase that precedes a part does not count towards the time limit of the part.
+                    if record.part == PARSE_PART {
+                        timer = Instant::now();
+                    }
+                    records.push(record);
+                }
+                None => println!("{line}"),
+            },
+            Err(RecvTimeoutError::Timeout) => {
+                // the process may have exited in the meantime, in which case there is nothing to kill.
+                let _ = child.kill();
+                let _ = child.wait();
+                return abort_record(part, &Abort::Timeout(limits.timeout.unwrap_or_default()));
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    let status = match child.wait() {
+        Ok(status) => status,
+        Err(e) => return abort_record(part, &Abort::Panic(e.to_string())),
+    };
+
+    if stderr_thread.join().unwrap_or_default() {
+        return abort_record(part, &Abort::OutOfMemory(limits.memory.unwrap_or_default()));
     }
+
+    // a part process that aborted while parsing reports the parse phase instead of its part.
+    records
+        .iter()
+        .find(|record| record.part == part)
+        .or_else(|| records.iter().find(|record| record.status.is_aborted()))
+        .map(|record| PartRecord {
+            part,
+            ..record.clone()
+        })
+        .unwrap_or_else(|| {
+            let abort = Abort::Panic(format!("part process exited unexpectedly ({status})"));
+            abort_record(part, &abort)
+        })
+}
+
+/// Runs a function, catching panics. In a part process, the function runs with the memory limit,
+/// and the process aborts if it exceeds it. The time limit is enforced by the process that started it.
+/// Outside of part processes, limits are not enforced, see [`run_isolated`].
+fn run_limited<R>(func: impl FnOnce() -> R, limits: Limits) -> Result<R, Abort> {
+    if !is_part_process() {
+        return catch_panic(func).map_err(Abort::Panic);
+    }
+
+    alloc::set_limit(limits.memory);
+    let result = catch_panic(func);
+    alloc::set_limit(None);
+
+    result.map_err(Abort::Panic)
+}
+
+thread_local! {
+    /// Whether panics of the current thread are caught by [`catch_panic`].
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+static PANIC_HOOK: Once = Once::new();
+
+/// Runs a function, catching panics. Returns the panic message and location on panic.
+/// A panic hook is installed once that keeps caught panics from being reported twice.
+/// Other panics, e.g. of threads spawned by the function, are reported by the previous hook.
+fn catch_panic<R>(func: impl FnOnce() -> R) -> Result<R, String> {
+    PANIC_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CATCHING.with(Cell::get) {
+                let location = info.location().map(ToString::to_string);
+                PANIC_LOCATION.with(|cell| *cell.borrow_mut() = location);
+            } else {
+                previous_hook(info);
+            }
+        }));
+    });
+
+    let was_catching = IS_CATCHING.with(|cell| cell.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING.with(|cell| cell.set(was_catching));
+
+    result.map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".into());
+
+        match PANIC_LOCATION.with(|cell| cell.borrow_mut().take()) {
+            Some(location) => format!("panicked at {location}: {message}"),
+            None => format!("panicked: {message}"),
+        }
+    })
+}
+
+/// The outcome of [`run_timed`]: the result, its duration, and the benchmark and heap statistics if requested.
+type TimedRun<T> = (T, Duration, Option<BenchStats>, Option<HeapStats>);
+
+/// Run a solution part. The behavior differs depending on whether we are benching:
+///  1. by default, the function is executed once.
+///  2. with `--time`, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// With `--memory`, the heap usage of the first run is profiled.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    is_timed: bool,
+    is_profiled: bool,
+    is_quiet: bool,
+) -> TimedRun<T> {
     let timer = Instant::now();
-    let result = {
+    let (result, heap) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        let profiler = (is_profiled && alloc::IS_COUNTING).then(alloc::HeapProfiler::start);
+        let result = func(input);
+        (result, profiler.map(|profiler| profiler.finish()))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if is_timed {
+        let (duration, stats) = bench(func, input, &base_time, is_quiet);
+        (result, duration, Some(stats), heap)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, None, heap)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Bench a solution part. A tenth of the iterations is run as warmup and discarded.
+/// Samples outside of the Tukey fences (1.5 times the interquartile range) are rejected as outliers.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    is_quiet: bool,
+) -> (Duration, BenchStats) {
+    if !is_quiet {
+        let mut stdout = stdout();
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input.clone())));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -71,30 +722,160 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
+    let stats = sample_stats(&timers);
+
     (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(stats.mean as u64),
+        stats,
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
+/// Computes the statistics of a set of samples, after rejecting outliers.
+#[allow(clippy::cast_precision_loss)]
+fn sample_stats(timers: &[Duration]) -> BenchStats {
+    let mut nanos: Vec<f64> = timers.iter().map(|t| t.as_nanos() as f64).collect();
+    nanos.sort_unstable_by(f64::total_cmp);
+
+    let q1 = percentile(&nanos, 0.25);
+    let q3 = percentile(&nanos, 0.75);
+    let fence = 1.5 * (q3 - q1);
+
+    let kept: Vec<f64> = nanos
         .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+        .copied()
+        .filter(|x| *x >= q1 - fence && *x <= q3 + fence)
+        .collect();
+
+    let count = kept.len() as f64;
+    let mean = kept.iter().sum::<f64>() / count;
+    let variance = kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+
+    BenchStats {
+        samples: kept.len() as u64,
+        outliers: (nanos.len() - kept.len()) as u64,
+        mean,
+        median: percentile(&kept, 0.5),
+        min: kept.first().copied().unwrap_or_default(),
+        max: kept.last().copied().unwrap_or_default(),
+        stddev: variance.sqrt(),
+        p95: percentile(&kept, 0.95),
+    }
+}
+
+/// Nearest-rank percentile of a sorted slice.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// Whether the solution was called with `--format json`.
+fn is_json_output() -> bool {
+    let args: Vec<String> = env::args().collect();
+    args.windows(2)
+        .any(|w| w[0] == "--format" && w[1] == "json")
+}
+
+pub(crate) fn format_duration(duration: &Duration, stats: Option<&BenchStats>) -> String {
+    match stats {
+        None => format!(" ({duration:.1?})"),
+        Some(stats) => format!(
+            " ({duration:.1?} @ {} samples; median {}, min {}, max {}, stddev {}, p95 {}, outliers {})",
+            stats.samples,
+            format_nanos(stats.median),
+            format_nanos(stats.min),
+            format_nanos(stats.max),
+            format_nanos(stats.stddev),
+            format_nanos(stats.p95),
+            stats.outliers
+        ),
+    }
+}
+
+/// The heap usage of a part, e.g. ` [peak 1.5 MiB, 1200 allocations, 4.0 MiB allocated]`.
+pub(crate) fn format_heap(heap: Option<&HeapStats>) -> String {
+    heap.map_or_else(String::new, |heap| {
+        format!(
+            " [peak {}, {} allocations, {} allocated]",
+            format_bytes(heap.peak_bytes),
+            heap.allocations,
+            format_bytes(heap.allocated_bytes)
+        )
+    })
+}
+
+/// A number of bytes in the largest binary unit that keeps it above 1, e.g. `1.5 MiB`.
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/// The label of a part in the output, e.g. `Part 1`.
+pub(crate) fn part_label(part: u8) -> String {
+    if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// Print the record of a phase that ran in another process.
+pub(crate) fn print_record(record: &PartRecord) {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(record.duration_nanos as u64);
+    let duration_str =
+        format_duration(&duration, record.stats.as_ref()) + &format_heap(record.heap.as_ref());
+
+    if record.status.is_aborted() || record.status == PartStatus::Error {
+        print_abort(record);
+    } else if record.part == PARSE_PART {
+        print_parse_result(&duration_str);
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        print_result(&record.answer, &part_label(record.part), &duration_str);
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Print a part that was aborted, with the reason it was aborted.
+pub(crate) fn print_abort(record: &PartRecord) {
+    print!("\r");
+    println!(
+        "{}: ✖ {}",
+        part_label(record.part),
+        record.error.as_deref().unwrap_or_default()
+    );
+}
+
+pub(crate) fn print_parse_result(duration_str: &str) {
+    print!("\r");
+    println!("Parse:{duration_str}");
+}
+
+pub(crate) fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -131,12 +912,17 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the part was run against the puzzle input.
+///  4. the answer ledger does not already know the answer to be wrong, or the part to be solved.
+///
+/// The verdict of the server is recorded in the answer ledger.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    input_id: &str,
+) -> Option<Result<aoc_client::Submission, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +945,216 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let source = InputSource::from_args(&args);
+    if !source.is_canonical() {
+        println!("Not submitting result: it was computed for {source}, not the puzzle input.");
+        return None;
+    }
+
+    let answer = result.to_string();
+    let mut ledger = Answers::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Not submitting result: {e}");
         process::exit(1);
+    });
+
+    if let Err(rejection) = ledger.check(puzzle, part, input_id, &answer) {
+        println!("Not submitting result: {rejection}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let submission = aoc_client::submit(puzzle, part, &answer);
+
+    match &submission {
+        Ok(submission) if submission.verdict == aoc_client::Verdict::Unknown => {
+            eprintln!(
+                "Could not recognise the response of the server, the answer is not recorded."
+            );
+        }
+        Ok(submission) => {
+            ledger.record(puzzle, part, input_id, &answer, submission.verdict);
+            if let Err(e) = ledger.store_file() {
+                eprintln!("Failed to store answer ledger: {e}");
+            }
+        }
+        Err(_) => {}
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(submission)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        execute_part, format_bytes, run_part_process, sample_stats, RunOptions, PARSE_PART,
+    };
+    use crate::template::{
+        alloc::ALLOC_ERROR_MESSAGE,
+        record::{PartRecord, PartStatus},
+        Limits,
+    };
+    use std::{process::Command, time::Duration};
+
+    #[test]
+    fn isolates_panics() {
+        let record = execute_part(
+            |input: &str| -> Option<u32> { Some(input.parse().unwrap()) },
+            "abc",
+            1,
+            RunOptions::default(),
+            true,
+        );
+        assert_eq!(record.status, PartStatus::Crashed);
+        assert_eq!(record.answer, None);
+
+        let error = record.error.unwrap();
+        assert!(error.starts_with("panicked at src/template/runner.rs:"));
+        assert!(error.ends_with(
+            "called `Result::unwrap()` on an `Err` value: ParseIntError { kind: InvalidDigit }"
+        ));
+
+        let record = execute_part(|_: &str| Some(1), "abc", 2, RunOptions::default(), true);
+        assert_eq!(record.status, PartStatus::Solved);
+    }
+
+    #[test]
+    fn reports_errors() {
+        let parse = |input: &str| input.parse::<u32>();
+        let record = execute_part(parse, "42", 1, RunOptions::default(), true);
+        assert_eq!(record.status, PartStatus::Solved);
+        assert_eq!(record.answer.as_deref(), Some("42"));
+
+        let record = execute_part(parse, "abc", 2, RunOptions::default(), true);
+        assert_eq!(record.status, PartStatus::Error);
+        assert_eq!(record.answer, None);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("invalid digit found in string")
+        );
+    }
+
+    /// A shell command that stands in for a part process.
+    #[cfg(unix)]
+    fn part_process(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn enforces_limits() {
+        let limits = Limits {
+            timeout: Some(Duration::from_millis(200)),
+            memory: Some(16 * 1024 * 1024),
+        };
+
+        let solved = execute_part(|_: &str| Some(1), "abc", 1, RunOptions::default(), true);
+        let script = format!("echo '{}'", solved.to_json_line());
+        let record = run_part_process(part_process(&script), "", 1, limits);
+        assert_eq!(record.status, PartStatus::Solved);
+        assert_eq!(record.answer.as_deref(), Some("1"));
+
+        let record = run_part_process(part_process("sleep 5"), "", 1, limits);
+        assert_eq!(record.status, PartStatus::Timeout);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("TIMEOUT (exceeded the time limit of 200ms)")
+        );
+
+        let script = format!("echo '{ALLOC_ERROR_MESSAGE}1024 bytes failed' >&2; kill -ABRT $$");
+        let record = run_part_process(part_process(&script), "", 2, limits);
+        assert_eq!(record.status, PartStatus::OutOfMemory);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("OOM (exceeded the memory limit of 16 MiB)")
+        );
+
+        let record = run_part_process(part_process("exit 1"), "", 2, limits);
+        assert_eq!(record.status, PartStatus::Crashed);
+        assert!(record
+            .error
+            .unwrap()
+            .starts_with("part process exited unexpectedly"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn reports_aborted_parse_phases_of_parts() {
+        let aborted = PartRecord {
+            part: PARSE_PART,
+            status: PartStatus::Crashed,
+            answer: None,
+            duration_nanos: 0.0,
+            samples: 0,
+            stats: None,
+            heap: None,
+            error: Some("panicked: invalid input".into()),
+        };
+        let script = format!("echo '{}'", aborted.to_json_line());
+        let record = run_part_process(part_process(&script), "", 2, Limits::default());
+        assert_eq!(record.part, 2);
+        assert_eq!(record.status, PartStatus::Crashed);
+        assert_eq!(record.error.as_deref(), Some("panicked: invalid input"));
+    }
+
+    #[test]
+    fn profiles_heap_usage() {
+        let allocate = |_: &str| {
+            let buffers: Vec<Vec<u8>> = (0..4).map(|_| vec![0; 1024 * 1024]).collect();
+            Some(buffers.len())
+        };
+
+        let profiled = RunOptions {
+            is_profiled: true,
+            ..Default::default()
+        };
+        let record = execute_part(allocate, "abc", 1, profiled, true);
+        let heap = record.heap.unwrap();
+        assert!(heap.allocations >= 5);
+        assert!(heap.allocated_bytes >= 4 * 1024 * 1024);
+        // other tests allocate at the same time, so the peak is not exact.
+        assert!(heap.peak_bytes <= heap.allocated_bytes);
+
+        let record = execute_part(allocate, "abc", 1, RunOptions::default(), true);
+        assert_eq!(record.heap, None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn reads_options_from_args() {
+        let args: Vec<String> = ["--time", "--memory", "--part", "2", "--timeout", "5"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let options = RunOptions::from_args(&args);
+        assert!(options.is_timed && options.is_profiled);
+        assert!(!options.is_selected(1) && options.is_selected(2));
+        assert_eq!(options.limits.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(RunOptions::from_args(&options.to_args()), options);
+    }
+
+    #[test]
+    fn computes_sample_stats() {
+        let timers: Vec<Duration> = [10, 12, 11, 13, 10, 11, 12, 10, 11, 1000]
+            .into_iter()
+            .map(Duration::from_nanos)
+            .collect();
+
+        let stats = sample_stats(&timers);
+        assert_eq!(stats.samples, 9);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 13.0);
+        assert_eq!(stats.median, 11.0);
+        assert_eq!(stats.p95, 13.0);
+        assert!((stats.mean - 100.0 / 9.0).abs() < 1e-9);
+    }
 }
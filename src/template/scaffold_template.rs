@@ -0,0 +1,155 @@
+use std::{error::Error, fmt::Display, fs};
+
+use crate::template::PuzzleId;
+
+/// Templates that come with the repository: name, source and default answer type.
+const BUILTIN_TEMPLATES: [(&str, &str, &str); 4] = [
+    (
+        "default",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+        "u32",
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+        "usize",
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+        "usize",
+    ),
+    (
+        "vm",
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/templates/vm.txt")),
+        "String",
+    ),
+];
+
+/// The answer type of templates in `./templates` that is used unless `--answer-type` is passed.
+const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// Folder of the templates of a project. These take precedence over the built-in templates.
+const PROJECT_TEMPLATES_PATH: &str = "./templates";
+
+/// A template for the module of a solution, with the placeholders
+/// `%YEAR%`, `%DAY_NUMBER%`, `%DAY%`, `%TITLE%` and `%ANSWER_TYPE%`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScaffoldTemplate {
+    pub name: String,
+    pub source: String,
+    pub answer_type: String,
+}
+
+impl ScaffoldTemplate {
+    /// Loads a template from `./templates/{name}.txt`, or one of the built-in templates.
+    pub fn load(name: &str) -> Result<Self, UnknownTemplateError> {
+        if let Ok(source) = fs::read_to_string(format!("{PROJECT_TEMPLATES_PATH}/{name}.txt")) {
+            return Ok(ScaffoldTemplate {
+                name: name.to_string(),
+                source,
+                answer_type: DEFAULT_ANSWER_TYPE.to_string(),
+            });
+        }
+
+        BUILTIN_TEMPLATES
+            .iter()
+            .find(|(builtin, _, _)| *builtin == name)
+            .map(|(name, source, answer_type)| ScaffoldTemplate {
+                name: (*name).to_string(),
+                source: (*source).to_string(),
+                answer_type: (*answer_type).to_string(),
+            })
+            .ok_or_else(|| UnknownTemplateError(name.to_string()))
+    }
+
+    /// Fills in the placeholders. Without a title, e.g. if the puzzle is not downloaded yet, the day is used.
+    pub fn render(&self, puzzle: PuzzleId, title: Option<&str>) -> String {
+        let day_number = puzzle.day.into_inner().to_string();
+        let title = title.map_or_else(|| format!("Day {day_number}"), str::to_string);
+
+        self.source
+            .replace("%YEAR%", &puzzle.year.to_string())
+            .replace("%DAY_NUMBER%", &day_number)
+            .replace("%DAY%", &puzzle.day.to_string())
+            .replace("%TITLE%", &title)
+            .replace("%ANSWER_TYPE%", &self.answer_type)
+    }
+}
+
+/// The title of a puzzle from its downloaded description, e.g. `Day 1: Historian Hysteria`.
+pub fn puzzle_title(markdown: &str) -> Option<String> {
+    let heading = markdown
+        .lines()
+        .find(|line| line.starts_with("## --- Day "))?;
+    let title = heading
+        .trim_start_matches("## ---")
+        .trim_end_matches("---")
+        .trim();
+    Some(title.to_string()).filter(|title| !title.is_empty())
+}
+
+/// An error which can be returned when loading a [`ScaffoldTemplate`].
+#[derive(Debug, Clone)]
+pub struct UnknownTemplateError(String);
+
+impl Error for UnknownTemplateError {}
+
+impl Display for UnknownTemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = BUILTIN_TEMPLATES.iter().map(|(name, _, _)| *name).collect();
+        write!(
+            f,
+            "unknown template `{}`, expecting one of {} or a file in `{PROJECT_TEMPLATES_PATH}`",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_title, ScaffoldTemplate};
+    use crate::{day, template::PuzzleId, year};
+
+    #[test]
+    fn renders_templates() {
+        let mut template = ScaffoldTemplate::load("vm").unwrap();
+        assert_eq!(template.answer_type, "String");
+
+        template.source = "//! %TITLE%\nsolution!(%YEAR%, %DAY_NUMBER%); // %DAY%.txt\n\
+            fn part_one() -> Option<%ANSWER_TYPE%>"
+            .to_string();
+        template.answer_type = "u64".to_string();
+
+        let puzzle = PuzzleId::new(year!(2024), day!(7));
+        assert_eq!(
+            template.render(puzzle, Some("Day 7: Bridge Repair")),
+            "//! Day 7: Bridge Repair\nsolution!(2024, 7); // 07.txt\nfn part_one() -> Option<u64>"
+        );
+        assert!(template.render(puzzle, None).starts_with("//! Day 7\n"));
+        assert!(ScaffoldTemplate::load("unknown").is_err());
+    }
+
+    #[test]
+    fn finds_puzzle_titles() {
+        let markdown =
+            "## --- Day 1: Historian Hysteria ---\n\nThe *Chief Historian* is missing.\n";
+        assert_eq!(
+            puzzle_title(markdown).as_deref(),
+            Some("Day 1: Historian Hysteria")
+        );
+        assert_eq!(puzzle_title("No heading.\n"), None);
+    }
+}
@@ -1,17 +1,133 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{environment::Environment, record::PartStatus, Day, PuzzleId, Year};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// The version of the format of `data/timings.json`.
+/// Version 1 stored durations as display strings, e.g. `"74.1ms"`, and did not have a `version` field.
+const SCHEMA_VERSION: u64 = 2;
+
 /// Represents benchmark times for a single day.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Timing {
+    pub year: Year,
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    /// Timing of the parse phase, for solutions that have one.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+    /// Status of a part that ran without a timing, e.g. because it exceeded the time limit or has no answer.
+    pub part_1_status: Option<PartStatus>,
+    pub part_2_status: Option<PartStatus>,
+}
+
+/// Benchmark time of a single phase of a solution.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartTiming {
+    /// Mean duration of a run in nanoseconds.
+    pub nanos: f64,
+    /// Number of runs the duration was measured over. Timings migrated from version 1 of the file have 0.
+    pub samples: u64,
+    pub stats: Option<BenchStats>,
+    /// Heap usage of a single run, recorded by `cargo time --memory`.
+    pub heap: Option<HeapStats>,
+}
+
+/// Statistics of the benchmark samples of a single part. Durations are in nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BenchStats {
+    /// Number of samples, after outliers have been rejected.
+    pub samples: u64,
+    pub outliers: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub stddev: f64,
+    pub p95: f64,
+}
+
+/// Heap usage of a single run of a part, as counted by the global allocator.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Highest number of bytes allocated at the same time, on top of what was allocated before the part ran.
+    pub peak_bytes: u64,
+    pub allocations: u64,
+    /// Total number of bytes allocated, including memory that has been freed again.
+    pub allocated_bytes: u64,
+}
+
+impl Timing {
+    pub fn puzzle(&self) -> PuzzleId {
+        PuzzleId::new(self.year, self.day)
+    }
+
+    /// Timing of a part, where part `0` is the parse phase.
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            0 => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    fn phases(&self) -> impl Iterator<Item = &PartTiming> {
+        [&self.parse, &self.part_1, &self.part_2]
+            .into_iter()
+            .flatten()
+    }
+
+    /// Sum of the durations of the parse phase and both parts.
+    pub fn total_nanos(&self) -> f64 {
+        self.phases().map(|phase| phase.nanos).sum()
+    }
+
+    /// Heap usage of the whole day: the highest peak of its phases, and the sum of their allocations.
+    pub fn heap(&self) -> Option<HeapStats> {
+        self.phases().filter_map(|phase| phase.heap.as_ref()).fold(
+            None,
+            |total: Option<HeapStats>, heap| {
+                let total = total.unwrap_or_default();
+                Some(HeapStats {
+                    peak_bytes: total.peak_bytes.max(heap.peak_bytes),
+                    allocations: total.allocations + heap.allocations,
+                    allocated_bytes: total.allocated_bytes + heap.allocated_bytes,
+                })
+            },
+        )
+    }
+
+    /// Merges the parts that ran in `new` into this timing, and keeps the other parts.
+    /// A part ran if it has a timing or a status, e.g. `unsolved`. The parse phase runs with every part.
+    pub fn merge(&self, new: &Timing) -> Timing {
+        let mut merged = self.clone();
+
+        let ran_part_1 = new.part_1.is_some() || new.part_1_status.is_some();
+        if ran_part_1 {
+            merged.part_1.clone_from(&new.part_1);
+            merged.part_1_status = new.part_1_status;
+        }
+
+        let ran_part_2 = new.part_2.is_some() || new.part_2_status.is_some();
+        if ran_part_2 {
+            merged.part_2.clone_from(&new.part_2);
+            merged.part_2_status = new.part_2_status;
+        }
+
+        if ran_part_1 || ran_part_2 || new.parse.is_some() {
+            merged.parse.clone_from(&new.parse);
+        }
+
+        merged
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -19,6 +135,8 @@ pub struct Timing {
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// The machine and toolchain the timings were measured with. Files written before it was recorded have none.
+    pub environment: Option<Environment>,
 }
 
 impl Timings {
@@ -30,47 +148,141 @@ impl Timings {
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
-
-        match s {
-            Ok(timings) => timings,
-            Err(e) => {
-                eprintln!("{e}");
-                Timings::default()
-            }
+    /// Files of an older version are read as the current version, and are migrated when timings are stored.
+    /// Files that cannot be read, e.g. of a newer version, are an error so they are not overwritten.
+    pub fn read_from_file() -> Result<Self, String> {
+        Timings::read_from_path(TIMINGS_FILE_PATH)
+    }
+
+    fn read_from_path(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Timings::parse(&s).map(|(timings, _)| timings),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(e.to_string()),
         }
+        .map_err(|e| format!("Failed to read \"{path}\": {e}"))
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Merge two sets of timings. The parts that ran in `new` overwrite the parts in `self`.
     pub fn merge(&self, new: &Self) -> Self {
-        let mut data: Vec<Timing> = vec![];
+        let mut data: Vec<Timing> = self.data.clone();
 
         for timing in &new.data {
-            data.push(timing.clone());
+            match data.iter_mut().find(|t| t.puzzle() == timing.puzzle()) {
+                Some(stored) => *stored = stored.merge(timing),
+                None => data.push(timing.clone()),
+            }
         }
 
-        for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
-                data.push(timing.clone());
-            }
+        data.sort_unstable_by_key(Timing::puzzle);
+        Timings {
+            data,
+            environment: new.environment.clone().or(self.environment.clone()),
         }
+    }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+    /// Checks that new timings are measured on the machine of these timings, so they are not mixed.
+    /// Timings without a recorded environment match any machine.
+    pub fn check_host(&self, environment: &Environment) -> Result<(), HostMismatchError> {
+        match &self.environment {
+            Some(stored) if !self.data.is_empty() && !stored.is_same_host(environment) => {
+                Err(HostMismatchError {
+                    stored: stored.to_string(),
+                    current: environment.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.puzzle() == puzzle && t.part_1.is_some() && t.part_2.is_some())
+    }
+
+    /// All years with timings, in ascending order.
+    pub fn years(&self) -> Vec<Year> {
+        let mut years: Vec<Year> = self.data.iter().map(|t| t.year).collect();
+        years.sort_unstable();
+        years.dedup();
+        years
+    }
+
+    /// Parses a timings file of any known version. Returns the timings and the version of the file.
+    fn parse(value: &str) -> Result<(Self, u64), String> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
+        Timings::from_json(&json)
+    }
+
+    /// Reads timings from a JSON document with `version` and `data` keys.
+    pub(crate) fn from_json(json: &JsonValue) -> Result<(Self, u64), String> {
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let version = match json.get("version") {
+            None => 1,
+            Some(v) => v
+                .get::<f64>()
+                .map(|version| *version as u64)
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        if version > SCHEMA_VERSION {
+            return Err(format!(
+                "timings file has version {version}, but this template only reads up to version {SCHEMA_VERSION}."
+            ));
+        }
+
+        let json_data = json
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        let data = json_data
+            .iter()
+            .map(|timing| match version {
+                1 => migrate::timing_from_v1(timing),
+                _ => Timing::try_from(timing),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let environment = match json.get("environment") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Environment::try_from(v)?),
+        };
+
+        Ok((Timings { data, environment }, version))
+    }
+}
+
+/// An error which can be returned when new timings are measured on another machine than the stored timings.
+#[derive(Debug)]
+pub struct HostMismatchError {
+    /// Description of the environment of the stored timings.
+    pub stored: String,
+    /// Description of the environment of this process.
+    pub current: String,
+}
+
+impl std::error::Error for HostMismatchError {}
+
+impl std::fmt::Display for HostMismatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the stored timings were measured on {}, but this machine is {}",
+            self.stored, self.current
+        )
     }
 }
 
@@ -80,10 +292,19 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("version".into(), JsonValue::Number(SCHEMA_VERSION as f64));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
+        map.insert(
+            "environment".into(),
+            value
+                .environment
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
 
         JsonValue::Object(map)
     }
@@ -93,22 +314,7 @@ impl TryFrom<String> for Timings {
     type Error = String;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
-
-        let json_data = json
-            .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
-            .get("data")
-            .ok_or("expected JSON document to have key `data`.")?
-            .get::<Vec<JsonValue>>()
-            .ok_or("expected `json.data` to be an array.")?;
-
-        Ok(Timings {
-            data: json_data
-                .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
-        })
+        Timings::parse(&value).map(|(timings, _)| timings)
     }
 }
 
@@ -118,125 +324,441 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("year".into(), JsonValue::String(value.year.to_string()));
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        let phase = |phase: Option<&PartTiming>| phase.map_or(JsonValue::Null, JsonValue::from);
+
+        map.insert("parse".into(), phase(value.parse.as_ref()));
+        map.insert("part_1".into(), phase(value.part_1.as_ref()));
+        map.insert("part_2".into(), phase(value.part_2.as_ref()));
+
+        let status = |status: Option<PartStatus>| {
+            status.map_or(JsonValue::Null, |s| JsonValue::String(s.to_string()))
+        };
+
+        map.insert("part_1_status".into(), status(value.part_1_status));
+        map.insert("part_2_status".into(), status(value.part_2_status));
 
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Timing {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing to be a JSON object.")?;
+
+        let (year, day) = read_puzzle(json)?;
+
+        let read_phase = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => PartTiming::try_from(v)
+                .map(Some)
+                .map_err(|e| format!("timing.{key}: {e}")),
+        };
+
+        Ok(Timing {
+            year,
+            day,
+            parse: read_phase("parse")?,
+            part_1: read_phase("part_1")?,
+            part_2: read_phase("part_2")?,
+            part_1_status: read_status(json, "part_1_status")?,
+            part_2_status: read_status(json, "part_2_status")?,
+        })
+    }
+}
+
+fn read_puzzle(json: &HashMap<String, JsonValue>) -> Result<(Year, Day), String> {
+    let day = json
+        .get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or("Expected timing.day to be a Day struct.")?;
+
+    // timings written before multi-year support belong to the configured year.
+    let year = match json.get("year") {
+        None => Year::from_env(),
+        Some(v) => v.get::<String>().and_then(|year| Year::from_str(year).ok()),
+    }
+    .ok_or("Expected timing.year to be a Year struct.")?;
+
+    Ok((year, day))
+}
+
+/// Statuses are only recorded for aborted parts.
+fn read_status(json: &HashMap<String, JsonValue>, key: &str) -> Result<Option<PartStatus>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => v
+            .get::<String>()
+            .ok_or(format!("Expected timing.{key} to be null or string."))?
+            .parse()
+            .map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "heap".into(),
+            value.heap.as_ref().map_or(JsonValue::Null, JsonValue::from),
         );
 
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected part timing.{key} to be a number."))
+        };
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        let heap = match json.get("heap") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(HeapStats::try_from(v)?),
+        };
+
+        Ok(PartTiming {
+            nanos: number("nanos")?,
+            samples: number("samples")? as u64,
+            stats,
+            heap,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        Ok(BenchStats {
+            samples: number("samples")? as u64,
+            outliers: number("outliers")? as u64,
+            mean: number("mean")?,
+            median: number("median")?,
+            min: number("min")?,
+            max: number("max")?,
+            stddev: number("stddev")?,
+            p95: number("p95")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HeapStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HeapStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert(
+            "allocated_bytes".into(),
+            JsonValue::Number(value.allocated_bytes as f64),
         );
 
         JsonValue::Object(map)
     }
 }
 
-impl TryFrom<&JsonValue> for Timing {
+impl TryFrom<&JsonValue> for HeapStats {
     type Error = String;
 
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected heap stats.{key} to be a number."))
+        };
+
+        Ok(HeapStats {
+            peak_bytes: number("peak_bytes")? as u64,
+            allocations: number("allocations")? as u64,
+            allocated_bytes: number("allocated_bytes")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads timings of version 1 of the file, which stored durations as display strings.
+mod migrate {
+    use std::{collections::HashMap, time::Duration};
+    use tinyjson::JsonValue;
+
+    use super::{read_puzzle, read_status, BenchStats, HeapStats, PartTiming, Timing};
+
+    pub fn timing_from_v1(value: &JsonValue) -> Result<Timing, String> {
         let json = value
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
-
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
+        let (year, day) = read_puzzle(json)?;
+
+        // the total is computed from the parts since version 2, but has always been required.
+        json.get("total_nanos")
+            .and_then(|v| v.get::<f64>())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let read_phase = |key: &str, required: bool| -> Result<Option<PartTiming>, String> {
+            let duration = match json.get(key) {
+                None if required => {
+                    return Err(format!("Expected timing.{key} to be null or string."))
+                }
+                None => return Ok(None),
+                Some(v) if v.is_null() => return Ok(None),
+                Some(v) => v
+                    .get::<String>()
+                    .ok_or(format!("Expected timing.{key} to be null or string."))?,
+            };
+
+            // stats and heap usage are optional to support files written before they were recorded.
+            let read_optional = |key: String| json.get(&key).filter(|v| !v.is_null());
+            let stats = read_optional(format!("{key}_stats"))
+                .map(BenchStats::try_from)
+                .transpose()?;
+            let heap = read_optional(format!("{key}_heap"))
+                .map(HeapStats::try_from)
+                .transpose()?;
+
+            // the mean of the stats is exact, the display string is rounded.
+            let nanos = match &stats {
+                Some(stats) => stats.mean,
+                None => parse_duration(duration).ok_or(format!(
+                    "Expected timing.{key} to be a duration, e.g. `1.5ms`."
+                ))?,
+            };
+
+            Ok(Some(PartTiming {
+                nanos,
+                samples: stats.as_ref().map_or(0, |s| s.samples),
+                stats,
+                heap,
+            }))
+        };
+
         Ok(Timing {
+            year,
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            parse: read_phase("parse", false)?,
+            part_1: read_phase("part_1", true)?,
+            part_2: read_phase("part_2", true)?,
+            part_1_status: read_status(json, "part_1_status")?,
+            part_2_status: read_status(json, "part_2_status")?,
         })
     }
+
+    /// Parses a duration in the debug format of [`Duration`], e.g. `74.1ms` or `950.0ns`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn parse_duration(s: &str) -> Option<f64> {
+        let (value, unit) = s.split_at(s.find(|c: char| c.is_alphabetic())?);
+        let scale = match unit {
+            "ns" => 1,
+            "µs" => Duration::from_micros(1).as_nanos(),
+            "ms" => Duration::from_millis(1).as_nanos(),
+            "s" => Duration::from_secs(1).as_nanos(),
+            _ => return None,
+        };
+        value.parse::<f64>().ok().map(|value| value * scale as f64)
+    }
 }
 
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{day, year};
 
-    use super::{Timing, Timings};
+    use super::{Environment, PartTiming, Timing, Timings};
+
+    fn part(nanos: f64) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos,
+            samples: 10,
+            ..Default::default()
+        })
+    }
+
+    fn environment(host: &str) -> Environment {
+        Environment {
+            host: host.into(),
+            cpu: "Apple M2".into(),
+            cores: 8,
+            os: "macos-aarch64".into(),
+            rustc: "1.83.0".into(),
+            profile: "release".into(),
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
+                    year: year!(2024),
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    parse: None,
+                    part_1: part(1e+10),
+                    part_2: part(2e+10),
+                    part_1_status: None,
+                    part_2_status: None,
                 },
                 Timing {
+                    year: year!(2024),
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    parse: None,
+                    part_1: part(3e+10),
+                    part_2: part(4e+10),
+                    part_1_status: None,
+                    part_2_status: None,
                 },
                 Timing {
+                    year: year!(2024),
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: part(4e+10),
                     part_2: None,
-                    total_nanos: 4e+10,
+                    part_1_status: None,
+                    part_2_status: None,
                 },
             ],
+            environment: None,
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{record::PartStatus, timings::Timings},
+            year,
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "year": "2024", "day": "01", "parse": null, "part_1": { "nanos": 1500000, "samples": 12, "stats": null, "heap": null }, "part_2": null, "part_1_status": null, "part_2_status": "timeout" }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.year, year!(2024));
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1_500_000_f64);
+            assert_eq!(part_1.samples, 12);
+            assert_eq!(part_1.stats, None);
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_2_status, Some(PartStatus::Timeout));
+            assert_eq!(timing.total_nanos(), 1_500_000_f64);
         }
 
         #[test]
         fn handles_empty_timings() {
-            let json = r#"{ "data": [] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 0);
         }
 
+        #[test]
+        fn rejects_newer_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            let error = Timings::try_from(json).unwrap_err();
+            assert!(error.contains("version 3"));
+        }
+
+        #[test]
+        fn refuses_unreadable_files() {
+            let path =
+                std::env::temp_dir().join(format!("aoc-timings-{}.json", std::process::id()));
+            let path = path.to_str().unwrap();
+
+            assert_eq!(Timings::read_from_path(path).unwrap().data.len(), 0);
+
+            std::fs::write(path, r#"{ "version": 3, "data": [] }"#).unwrap();
+            let error = Timings::read_from_path(path).unwrap_err();
+            std::fs::remove_file(path).unwrap();
+
+            assert!(error.contains("version 3"));
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_invalid_json() {
@@ -244,16 +766,113 @@ mod tests {
             Timings::try_from(json).unwrap();
         }
 
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1 } }] }"#
+                .to_string();
+            Timings::try_from(json).unwrap();
+        }
+    }
+
+    mod migration {
+        use crate::{
+            day,
+            template::{record::PartStatus, timings::Timings},
+            year,
+        };
+
+        use super::super::migrate::parse_duration;
+
+        #[test]
+        fn handles_json_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 1);
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(1));
+            // files without a year belong to `AOC_YEAR`.
+            assert_eq!(timing.year, year!(2024));
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1_000_000_f64);
+            assert_eq!(part_1.samples, 0);
+            assert_eq!(part_1.stats, None);
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.parse, None);
+        }
+
+        #[test]
+        fn handles_json_timing_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "outliers": 1, "mean": 1000400, "median": 900000, "min": 800000, "max": 1500000, "stddev": 1000, "p95": 1400000 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.as_ref().unwrap();
+            // the mean of the stats is more precise than the display string.
+            assert_eq!(part_1.nanos, 1_000_400_f64);
+            assert_eq!(part_1.samples, 10);
+            let stats = part_1.stats.as_ref().unwrap();
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(stats.median, 900_000_f64);
+            assert_eq!(stats.p95, 1_400_000_f64);
+        }
+
+        #[test]
+        fn handles_json_parse_timings() {
+            let json = r#"{ "data": [{ "year": "2024", "day": "01", "parse": "2.5µs", "part_1": "1ms", "part_2": null, "total_nanos": 3000000, "parse_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].parse.as_ref().unwrap().nanos, 2_500_f64);
+        }
+
+        #[test]
+        fn handles_json_part_statuses() {
+            let json = r#"{ "data": [{ "year": "2024", "day": "20", "part_1": null, "part_2": "1ms", "total_nanos": 1000000, "part_1_status": "timeout", "part_2_status": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].part_1_status, Some(PartStatus::Timeout));
+            assert_eq!(timings.data[0].part_2_status, None);
+        }
+
+        #[test]
+        fn handles_json_heap_stats() {
+            let json = r#"{ "data": [{ "year": "2024", "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_1_heap": { "peak_bytes": 1024, "allocations": 2, "allocated_bytes": 1536 }, "part_2_heap": { "peak_bytes": 4096, "allocations": 3, "allocated_bytes": 8192 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = &timings.data[0];
+            assert_eq!(
+                timing
+                    .part_1
+                    .as_ref()
+                    .unwrap()
+                    .heap
+                    .as_ref()
+                    .unwrap()
+                    .peak_bytes,
+                1024
+            );
+
+            let heap = timing.heap().unwrap();
+            assert_eq!(heap.peak_bytes, 4096);
+            assert_eq!(heap.allocations, 5);
+            assert_eq!(heap.allocated_bytes, 9728);
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
+
+        #[test]
+        fn parses_durations() {
+            assert_eq!(parse_duration("74.0ns"), Some(74.0));
+            assert_eq!(parse_duration("1.5µs"), Some(1_500.0));
+            assert_eq!(parse_duration("74.1ms"), Some(74_100_000.0));
+            assert_eq!(parse_duration("2.0s"), Some(2e+9));
+            assert_eq!(parse_duration("fast"), None);
+        }
     }
 
     mod serialization {
-        use super::get_mock_timings;
+        use super::{environment, get_mock_timings};
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -261,11 +880,10 @@ mod tests {
         fn serializes_timings() {
             let timings = get_mock_timings();
             let value = JsonValue::try_from(timings).unwrap();
+            let json = value.get::<HashMap<String, JsonValue>>().unwrap();
+            assert_eq!(json.get("version").unwrap().get::<f64>(), Some(&2.0));
             assert_eq!(
-                value
-                    .get::<HashMap<String, JsonValue>>()
-                    .unwrap()
-                    .get("data")
+                json.get("data")
                     .unwrap()
                     .get::<Vec<JsonValue>>()
                     .unwrap()
@@ -273,75 +891,110 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data, timings.data);
+            assert_eq!(parsed.environment, None);
+        }
+
+        #[test]
+        fn roundtrips_environments() {
+            let mut timings = get_mock_timings();
+            timings.environment = Some(environment("laptop"));
+            let json = JsonValue::from(timings).stringify().unwrap();
+            assert_eq!(
+                Timings::try_from(json).unwrap().environment,
+                Some(environment("laptop"))
+            );
+        }
     }
 
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                timings::{Timing, Timings},
+                PuzzleId,
+            },
+            year,
         };
 
-        #[test]
-        fn handles_completed_days() {
-            let timings = Timings {
+        use super::part;
+
+        fn timing(part_1: bool, part_2: bool) -> Timings {
+            Timings {
                 data: vec![Timing {
+                    year: year!(2024),
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    parse: None,
+                    part_1: if part_1 { part(1e+6) } else { None },
+                    part_2: if part_2 { part(2e+6) } else { None },
+                    part_1_status: None,
+                    part_2_status: None,
                 }],
-            };
+                environment: None,
+            }
+        }
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+        #[test]
+        fn handles_completed_days() {
+            assert_eq!(
+                timing(true, true).is_day_complete(PuzzleId::new(year!(2024), day!(1))),
+                true
+            );
         }
 
         #[test]
         fn handles_partial_days() {
-            let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
-                }],
-            };
-
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(
+                timing(true, false).is_day_complete(PuzzleId::new(year!(2024), day!(1))),
+                false
+            );
         }
 
         #[test]
         fn handles_uncompleted_days() {
-            let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
-            };
-
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(
+                timing(false, false).is_day_complete(PuzzleId::new(year!(2024), day!(1))),
+                false
+            );
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                record::PartStatus,
+                timings::{Timing, Timings},
+            },
+            year,
         };
 
-        use super::get_mock_timings;
+        use super::{environment, get_mock_timings, part};
+
+        fn timing(day: crate::template::Day, part_1: f64, part_2: f64) -> Timing {
+            Timing {
+                year: year!(2024),
+                day,
+                parse: None,
+                part_1: part(part_1),
+                part_2: part(part_2),
+                part_1_status: None,
+                part_2_status: None,
+            }
+        }
 
         #[test]
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![timing(day!(3), 1.0, 2.0)],
+                ..Default::default()
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -356,22 +1009,90 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![timing(day!(2), 1.0, 2.0)],
+                ..Default::default()
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos(), 3_f64);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn merges_single_parts() {
+            let timings = get_mock_timings();
+
+            let mut other = timing(day!(2), 0.0, 5e+10);
+            other.part_1 = None;
+            let merged = timings.merge(&Timings {
+                data: vec![other],
+                ..Default::default()
+            });
+
+            // part 1 did not run, so its stored timing is kept.
+            assert_eq!(merged.data[1].part_1.as_ref().unwrap().nanos, 3e+10);
+            assert_eq!(merged.data[1].part_2.as_ref().unwrap().nanos, 5e+10);
+        }
+
+        #[test]
+        fn merges_aborted_parts() {
+            let timings = get_mock_timings();
+
+            let mut other = timing(day!(1), 0.0, 0.0);
+            other.part_1 = None;
+            other.part_2 = None;
+            other.part_2_status = Some(PartStatus::Timeout);
+            let merged = timings.merge(&Timings {
+                data: vec![other],
+                ..Default::default()
+            });
+
+            assert_eq!(merged.data[0].part_1.as_ref().unwrap().nanos, 1e+10);
+            assert_eq!(merged.data[0].part_2, None);
+            assert_eq!(merged.data[0].part_2_status, Some(PartStatus::Timeout));
+        }
+
+        #[test]
+        fn merges_unsolved_parts() {
+            let timings = get_mock_timings();
+
+            let mut other = timing(day!(1), 0.0, 0.0);
+            other.part_1 = None;
+            other.part_2 = None;
+            other.part_1_status = Some(PartStatus::Unsolved);
+            other.part_2_status = Some(PartStatus::Error);
+            let merged = timings.merge(&Timings {
+                data: vec![other],
+                ..Default::default()
+            });
+
+            // both parts ran without a timing, so their stored timings are cleared.
+            assert_eq!(merged.data[0].part_1, None);
+            assert_eq!(merged.data[0].part_2, None);
+            assert_eq!(merged.data[0].part_1_status, Some(PartStatus::Unsolved));
+        }
+
+        #[test]
+        fn handles_timings_of_other_years() {
+            let timings = get_mock_timings();
+            let mut other = timing(day!(2), 0.0, 0.0);
+            other.year = year!(2023);
+            let merged = timings.merge(&Timings {
+                data: vec![other],
+                ..Default::default()
+            });
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[0].year, year!(2023));
+            assert_eq!(merged.data[0].day, day!(2));
+            assert_eq!(merged.data[2].year, year!(2024));
+            assert_eq!(merged.data[2].day, day!(2));
+            assert_eq!(merged.data[2].total_nanos(), 7e+10);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -387,5 +1108,37 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_the_newest_environment() {
+            let mut timings = get_mock_timings();
+            timings.environment = Some(environment("laptop"));
+
+            let merged = timings.merge(&Timings::default());
+            assert_eq!(merged.environment, Some(environment("laptop")));
+
+            let other = Timings {
+                environment: Some(environment("desktop")),
+                ..Default::default()
+            };
+            let merged = timings.merge(&other);
+            assert_eq!(merged.environment, Some(environment("desktop")));
+        }
+
+        #[test]
+        fn checks_hosts() {
+            let mut timings = get_mock_timings();
+            // timings stored before the environment was recorded match any host.
+            assert!(timings.check_host(&environment("desktop")).is_ok());
+
+            timings.environment = Some(environment("laptop"));
+            assert!(timings.check_host(&environment("laptop")).is_ok());
+            let error = timings.check_host(&environment("desktop")).unwrap_err();
+            assert!(error.stored.starts_with("`laptop`"));
+            assert!(error.current.starts_with("`desktop`"));
+
+            timings.data.clear();
+            assert!(timings.check_host(&environment("desktop")).is_ok());
+        }
     }
 }
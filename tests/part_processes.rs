@@ -0,0 +1,31 @@
+//! Runs solution binaries with limits, which run every part in a process of its own.
+use std::{
+    fs,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+#[test]
+fn passes_stdin_to_part_processes() {
+    let input = fs::read_to_string("data/2024/examples/01.txt").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_2024_01"))
+        .args(["--stdin", "--timeout", "10", "--format", "json"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(r#""answer":"11""#), "{stdout}");
+    assert!(stdout.contains(r#""answer":"31""#), "{stdout}");
+}